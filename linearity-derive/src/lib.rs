@@ -0,0 +1,186 @@
+/*
+    linearity: A library for branchless programming
+    Copyright (C) 2024  advantageous-overtake
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Derive macros for the [`linearity`](https://docs.rs/linearity) crate.
+//!
+//! These are re-exported by `linearity` behind its `derive` feature, prefer depending on it over this crate.
+
+#![deny(missing_docs)]
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields, Ident, Index};
+
+/// Integer representations a fieldless enum may select through.
+const REPR_LIST: &[&str] = &[
+    "u8", "i8", "u16", "i16", "u32", "i32", "u64", "i64", "u128", "i128", "usize", "isize",
+];
+
+/// Derives `linearity::Select`.
+///
+/// * Structs select field by field, every field must implement `Select`.
+/// * Enums must be fieldless and carry an integer `#[repr(...)]`, they select through their discriminants.
+///
+/// The type must also implement `Copy`.
+#[proc_macro_derive(Select)]
+pub fn derive_select(target_input: TokenStream) -> TokenStream {
+    let target_input = parse_macro_input!(target_input as DeriveInput);
+
+    expand_select(target_input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Expands the `Select` implementation for `target_input`.
+fn expand_select(mut target_input: DeriveInput) -> Result<TokenStream2, Error> {
+    let target_body = match &target_input.data {
+        Data::Struct(target_data) => select_struct(&target_data.fields),
+        Data::Enum(target_data) => {
+            if let Some(target_variant) = target_data
+                .variants
+                .iter()
+                .find(|target_variant| !matches!(target_variant.fields, Fields::Unit))
+            {
+                return Err(Error::new_spanned(
+                    target_variant,
+                    "`Select` can only be derived for fieldless enums",
+                ));
+            }
+
+            select_enum(&target_input)?
+        }
+        Data::Union(target_data) => {
+            return Err(Error::new_spanned(
+                target_data.union_token,
+                "`Select` cannot be derived for unions",
+            ))
+        }
+    };
+
+    // Every type parameter has to be selectable for its fields to be.
+    let target_parameters: Vec<Ident> = target_input
+        .generics
+        .type_params()
+        .map(|target_parameter| target_parameter.ident.clone())
+        .collect();
+
+    let target_where = target_input.generics.make_where_clause();
+
+    for target_parameter in target_parameters {
+        target_where
+            .predicates
+            .push(parse_quote!(#target_parameter: ::linearity::Select));
+    }
+
+    let target_name = &target_input.ident;
+    let (target_impl, target_type, target_where) = target_input.generics.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #target_impl ::linearity::Select for #target_name #target_type #target_where {
+            #[inline]
+            fn select(target_left: Self, target_right: Self, target_dependence: bool) -> Self {
+                #target_body
+            }
+        }
+    })
+}
+
+/// Builds a struct out of the selection of every field.
+fn select_struct(target_fields: &Fields) -> TokenStream2 {
+    match target_fields {
+        Fields::Named(target_fields) => {
+            let target_names = target_fields
+                .named
+                .iter()
+                .map(|target_field| &target_field.ident);
+
+            quote! {
+                Self {
+                    #(
+                        #target_names: ::linearity::Select::select(
+                            target_left.#target_names,
+                            target_right.#target_names,
+                            target_dependence,
+                        ),
+                    )*
+                }
+            }
+        }
+        Fields::Unnamed(target_fields) => {
+            let target_indices = (0..target_fields.unnamed.len()).map(Index::from);
+
+            quote! {
+                Self(
+                    #(
+                        ::linearity::Select::select(
+                            target_left.#target_indices,
+                            target_right.#target_indices,
+                            target_dependence,
+                        ),
+                    )*
+                )
+            }
+        }
+        Fields::Unit => quote! {
+            let _ = (target_left, target_right, target_dependence);
+
+            Self
+        },
+    }
+}
+
+/// Selects between the discriminants of a fieldless enum.
+fn select_enum(target_input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let mut target_repr = None;
+
+    for target_attribute in target_input
+        .attrs
+        .iter()
+        .filter(|target_attribute| target_attribute.path().is_ident("repr"))
+    {
+        target_attribute.parse_nested_meta(|target_meta| {
+            if let Some(target_ident) = target_meta.path.get_ident() {
+                if REPR_LIST.contains(&target_ident.to_string().as_str()) {
+                    target_repr = Some(target_ident.clone());
+                }
+            }
+
+            Ok(())
+        })?;
+    }
+
+    let Some(target_repr) = target_repr else {
+        return Err(Error::new(
+            Span::call_site(),
+            "`Select` requires fieldless enums to have an integer `#[repr(...)]`",
+        ));
+    };
+
+    Ok(quote! {
+        let target_discriminant = <#target_repr as ::linearity::Select>::select(
+            target_left as #target_repr,
+            target_right as #target_repr,
+            target_dependence,
+        );
+
+        // SAFETY: The discriminant is the one of either operand, so it names a variant of this enum.
+        unsafe { ::core::mem::transmute::<#target_repr, Self>(target_discriminant) }
+    })
+}
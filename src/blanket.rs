@@ -18,9 +18,13 @@
 
 //! Module for blanket implementations and miscenalleous traits.
 
-use crate::Linearity;
+use core::ptr::NonNull;
+
+use crate::{Filter, Linearity};
 
 /// Extension trait for pointers.
+///
+/// Implemented for thin pointers and [`NonNull`], see [`FatPointerExt`] for pointers to slices and `str`.
 pub trait PointerExt<T> {
     /// Selects between two pointers based on a predicate.
     ///
@@ -35,6 +39,24 @@ pub trait PointerExt<T> {
     ///
     /// Safety constrains are as per [`core::ptr::read`] for both inputs.
     unsafe fn select_deref(self, target_other: Self, target_predicate: bool) -> T;
+
+    /// Advances the pointer by `target_count` elements if `target_predicate` holds, otherwise leaves it in place.
+    ///
+    /// # Safety
+    ///
+    /// Safety constrains are as per [`pointer::add`] for `target_count`, regardless of `target_predicate`.
+    ///
+    /// [`pointer::add`]: https://doc.rust-lang.org/std/primitive.pointer.html#method.add
+    unsafe fn add_if(self, target_count: usize, target_predicate: bool) -> Self;
+
+    /// Offsets the pointer by `target_count` elements if `target_predicate` holds, otherwise leaves it in place.
+    ///
+    /// # Safety
+    ///
+    /// Safety constrains are as per [`pointer::offset`] for `target_count`, regardless of `target_predicate`.
+    ///
+    /// [`pointer::offset`]: https://doc.rust-lang.org/std/primitive.pointer.html#method.offset
+    unsafe fn offset_if(self, target_count: isize, target_predicate: bool) -> Self;
 }
 
 /// Extension trait for mutable pointers.
@@ -42,6 +64,40 @@ pub trait PointerMutExt<T>: PointerExt<T> {
     
 }
 
+/// Extension trait for fat pointers to slices and `str`.
+///
+/// Both the address and the length metadata are selected.
+pub trait FatPointerExt: Sized {
+    /// Selects between two pointers based on a predicate.
+    ///
+    /// # Safety
+    ///
+    /// Both pointers must be valid as per [`core::ptr::read`] for the selected one to be dereferenced.
+    unsafe fn select(self, target_other: Self, target_predicate: bool) -> Self;
+}
+
+/// Extension trait for references.
+///
+/// This is the safe counterpart of [`PointerExt`], both references are valid for the same lifetime so the selected one
+/// is as well. Implemented for references to sized types, slices and `str`.
+pub trait RefExt: Sized {
+    /// Selects between two references based on a predicate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use linearity::RefExt;
+    ///
+    /// let target_left = [1, 2, 3];
+    /// let target_right = [4, 5];
+    ///
+    /// let selected_value: &[i32] = RefExt::select(&target_left[..], &target_right[..], true);
+    ///
+    /// assert_eq!(selected_value, &[4, 5]);
+    /// ```
+    fn select(self, target_other: Self, target_predicate: bool) -> Self;
+}
+
 impl<T> PointerExt<T> for *const T
 where
     T: Copy,
@@ -61,6 +117,16 @@ where
 
         target_outcome as Self
     }
+
+    #[inline]
+    unsafe fn add_if(self, target_count: usize, target_predicate: bool) -> Self {
+        self.add(target_count.filter(Filter::new(target_predicate)))
+    }
+
+    #[inline]
+    unsafe fn offset_if(self, target_count: isize, target_predicate: bool) -> Self {
+        self.offset(target_count.filter(Filter::new(target_predicate)))
+    }
 }
 
 impl<T> PointerExt<T> for *mut T {
@@ -79,6 +145,254 @@ impl<T> PointerExt<T> for *mut T {
 
         target_outcome as Self
     }
+
+    #[inline]
+    unsafe fn add_if(self, target_count: usize, target_predicate: bool) -> Self {
+        self.add(target_count.filter(Filter::new(target_predicate)))
+    }
+
+    #[inline]
+    unsafe fn offset_if(self, target_count: isize, target_predicate: bool) -> Self {
+        self.offset(target_count.filter(Filter::new(target_predicate)))
+    }
+}
+
+impl<T> PointerExt<T> for NonNull<T> {
+    #[inline]
+    unsafe fn select_deref(self, target_other: Self, target_predicate: bool) -> T {
+        core::ptr::read(self.select(target_other, target_predicate).as_ptr())
+    }
+
+    #[inline]
+    unsafe fn select(self, target_other: Self, target_dependence: bool) -> Self {
+        let target_outcome = self
+            .as_ptr()
+            .select(target_other.as_ptr(), target_dependence);
+
+        // Both inputs are non-null, so is the selected one.
+        NonNull::new_unchecked(target_outcome)
+    }
+
+    #[inline]
+    unsafe fn add_if(self, target_count: usize, target_predicate: bool) -> Self {
+        NonNull::new_unchecked(self.as_ptr().add_if(target_count, target_predicate))
+    }
+
+    #[inline]
+    unsafe fn offset_if(self, target_count: isize, target_predicate: bool) -> Self {
+        NonNull::new_unchecked(self.as_ptr().offset_if(target_count, target_predicate))
+    }
 }
 
 impl<T> PointerMutExt<T> for *mut T where *mut T: PointerExt<T> {}
+
+impl<T> PointerMutExt<T> for NonNull<T> where NonNull<T>: PointerExt<T> {}
+
+impl<T> FatPointerExt for *const [T] {
+    #[inline]
+    unsafe fn select(self, target_other: Self, target_dependence: bool) -> Self {
+        let target_address = usize::select(self as *const T as usize, target_other as *const T as usize, target_dependence);
+        let target_length = usize::select(self.len(), target_other.len(), target_dependence);
+
+        core::ptr::slice_from_raw_parts(target_address as *const T, target_length)
+    }
+}
+
+impl<T> FatPointerExt for *mut [T] {
+    #[inline]
+    unsafe fn select(self, target_other: Self, target_dependence: bool) -> Self {
+        let target_address = usize::select(self as *mut T as usize, target_other as *mut T as usize, target_dependence);
+        let target_length = usize::select(self.len(), target_other.len(), target_dependence);
+
+        core::ptr::slice_from_raw_parts_mut(target_address as *mut T, target_length)
+    }
+}
+
+impl FatPointerExt for *const str {
+    #[inline]
+    unsafe fn select(self, target_other: Self, target_dependence: bool) -> Self {
+        FatPointerExt::select(self as *const [u8], target_other as *const [u8], target_dependence) as Self
+    }
+}
+
+impl FatPointerExt for *mut str {
+    #[inline]
+    unsafe fn select(self, target_other: Self, target_dependence: bool) -> Self {
+        FatPointerExt::select(self as *mut [u8], target_other as *mut [u8], target_dependence) as Self
+    }
+}
+
+impl<T> RefExt for &T {
+    #[inline]
+    fn select(self, target_other: Self, target_predicate: bool) -> Self {
+        let target_address = usize::select(self as *const T as usize, target_other as *const T as usize, target_predicate);
+
+        // SAFETY: Both references are valid for the same lifetime, the selected address is one of them.
+        unsafe { &*(target_address as *const T) }
+    }
+}
+
+impl<T> RefExt for &mut T {
+    #[inline]
+    fn select(self, target_other: Self, target_predicate: bool) -> Self {
+        let target_address = usize::select(self as *mut T as usize, target_other as *mut T as usize, target_predicate);
+
+        // SAFETY: Both exclusive references are consumed and valid for the same lifetime, the selected address is one of them.
+        unsafe { &mut *(target_address as *mut T) }
+    }
+}
+
+impl<T> RefExt for &[T] {
+    #[inline]
+    fn select(self, target_other: Self, target_predicate: bool) -> Self {
+        // SAFETY: Both references are valid for the same lifetime, the selected pointer is one of them.
+        unsafe { &*FatPointerExt::select(self as *const [T], target_other as *const [T], target_predicate) }
+    }
+}
+
+impl<T> RefExt for &mut [T] {
+    #[inline]
+    fn select(self, target_other: Self, target_predicate: bool) -> Self {
+        // SAFETY: Both exclusive references are consumed and valid for the same lifetime, the selected pointer is one of them.
+        unsafe { &mut *FatPointerExt::select(self as *mut [T], target_other as *mut [T], target_predicate) }
+    }
+}
+
+impl RefExt for &str {
+    #[inline]
+    fn select(self, target_other: Self, target_predicate: bool) -> Self {
+        // SAFETY: Both references are valid for the same lifetime, the selected pointer is one of them.
+        unsafe { &*FatPointerExt::select(self as *const str, target_other as *const str, target_predicate) }
+    }
+}
+
+impl RefExt for &mut str {
+    #[inline]
+    fn select(self, target_other: Self, target_predicate: bool) -> Self {
+        // SAFETY: Both exclusive references are consumed and valid for the same lifetime, the selected pointer is one of them.
+        unsafe { &mut *FatPointerExt::select(self as *mut str, target_other as *mut str, target_predicate) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::ptr::NonNull;
+
+    use super::{FatPointerExt, PointerExt, RefExt};
+
+    #[test]
+    fn select_non_null() {
+        let mut target_left = 3u32;
+        let mut target_right = 7u32;
+
+        let target_left_pointer = NonNull::from(&mut target_left);
+        let target_right_pointer = NonNull::from(&mut target_right);
+
+        for target_predicate in [false, true] {
+            let target_expect = [target_left_pointer, target_right_pointer][target_predicate as usize];
+
+            // SAFETY: Both pointers are valid for reads.
+            unsafe {
+                assert_eq!(target_left_pointer.select(target_right_pointer, target_predicate), target_expect);
+                assert_eq!(target_left_pointer.select_deref(target_right_pointer, target_predicate), [3, 7][target_predicate as usize]);
+            }
+        }
+    }
+
+    #[test]
+    fn add_if_offset_if() {
+        let mut target_values = [10u64, 11, 12, 13, 14];
+
+        let target_base = target_values.as_mut_ptr();
+
+        for target_predicate in [false, true] {
+            let target_step = target_predicate as usize;
+
+            // SAFETY: Every offset stays within `target_values`.
+            unsafe {
+                let target_middle = target_base.add(2);
+
+                assert_eq!(*(target_middle as *const u64).add_if(2, target_predicate), [12, 14][target_step]);
+                assert_eq!(*(target_middle as *const u64).offset_if(-2, target_predicate), [12, 10][target_step]);
+
+                assert_eq!(target_middle.add_if(1, target_predicate), target_middle.add(target_step));
+                assert_eq!(target_middle.offset_if(-1, target_predicate), target_middle.sub(target_step));
+
+                let target_pointer = NonNull::new_unchecked(target_middle);
+
+                assert_eq!(*target_pointer.add_if(2, target_predicate).as_ptr(), [12, 14][target_step]);
+                assert_eq!(*target_pointer.offset_if(-2, target_predicate).as_ptr(), [12, 10][target_step]);
+            }
+        }
+    }
+
+    #[test]
+    fn select_slice_pointers() {
+        let mut target_left = [1u16, 2, 3];
+        let mut target_right = [4u16, 5];
+
+        for target_predicate in [false, true] {
+            // SAFETY: Both pointers are valid, the selected one is only read through.
+            unsafe {
+                let target_value = FatPointerExt::select(&target_left[..] as *const [u16], &target_right[..] as *const [u16], target_predicate);
+
+                assert_eq!(&*target_value, [&target_left[..], &target_right[..]][target_predicate as usize]);
+
+                let target_value = FatPointerExt::select(&mut target_left[..] as *mut [u16], &mut target_right[..] as *mut [u16], target_predicate);
+
+                (*target_value)[0] += 10;
+            }
+        }
+
+        assert_eq!(target_left, [11, 2, 3]);
+        assert_eq!(target_right, [14, 5]);
+    }
+
+    #[test]
+    fn select_str_pointers() {
+        let mut target_left = String::from("left");
+        let mut target_right = String::from("right!");
+
+        for target_predicate in [false, true] {
+            // SAFETY: Both pointers are valid, the selected one is only read through.
+            unsafe {
+                let target_value = FatPointerExt::select(target_left.as_str() as *const str, target_right.as_str() as *const str, target_predicate);
+
+                assert_eq!(&*target_value, ["left", "right!"][target_predicate as usize]);
+
+                let target_value = FatPointerExt::select(target_left.as_mut_str() as *mut str, target_right.as_mut_str() as *mut str, target_predicate);
+
+                (*target_value).make_ascii_uppercase();
+            }
+        }
+
+        assert_eq!((target_left.as_str(), target_right.as_str()), ("LEFT", "RIGHT!"));
+    }
+
+    #[test]
+    fn select_references() {
+        let target_left = String::from("left");
+        let target_right = String::from("right");
+
+        for target_predicate in [false, true] {
+            let target_expect = ["left", "right"][target_predicate as usize];
+
+            assert_eq!(RefExt::select(&target_left, &target_right, target_predicate), target_expect);
+            assert_eq!(RefExt::select(target_left.as_str(), target_right.as_str(), target_predicate), target_expect);
+        }
+
+        let mut target_left = vec![1, 2];
+        let mut target_right = vec![3];
+
+        RefExt::select(&mut target_left, &mut target_right, true).push(4);
+        RefExt::select(&mut target_left[..], &mut target_right[..], false)[0] = 0;
+
+        let mut target_text = String::from("text");
+        let mut target_other = String::from("other");
+
+        RefExt::select(target_text.as_mut_str(), target_other.as_mut_str(), true).make_ascii_uppercase();
+
+        assert_eq!((target_left, target_right), (vec![0, 2], vec![3, 4]));
+        assert_eq!((target_text.as_str(), target_other.as_str()), ("text", "OTHER"));
+    }
+}
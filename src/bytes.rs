@@ -0,0 +1,179 @@
+/*
+    linearity: A library for branchless programming
+    Copyright (C) 2024  advantageous-overtake
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Bytewise selection for arbitrary types.
+//!
+//! See [`select_bytes`] for more information.
+
+use core::{
+    mem::{size_of, MaybeUninit},
+    num::{Saturating, Wrapping},
+    ptr,
+};
+
+use crate::{primitive::primitive_list, Filter};
+
+/// Marker trait for types without padding bytes.
+///
+/// Such types can be selected between through their byte representation, see [`select_bytes`].
+///
+/// # Safety
+///
+/// Every byte of every value of the type must be initialized, which rules out padding. Since a selection returns the
+/// bytes of one of its inputs, no constraint is placed on which bit patterns are valid.
+pub unsafe trait NoPadding: Copy {}
+
+/// Implements [`NoPadding`] for a set of types.
+macro_rules! impl_no_padding {
+    (
+        $(
+            $target_type:ty
+        )+
+    ) => {
+        $(
+            // SAFETY: Scalars have no padding.
+            unsafe impl NoPadding for $target_type {}
+        )+
+    };
+}
+
+primitive_list!(impl_no_padding);
+
+impl_no_padding!(bool char f32 f64);
+
+// SAFETY: Arrays have no padding between their elements, and elements without padding have none themselves.
+unsafe impl<T, const N: usize> NoPadding for [T; N] where T: NoPadding {}
+
+// SAFETY: `Wrapping` is a transparent wrapper.
+unsafe impl<T> NoPadding for Wrapping<T> where T: NoPadding {}
+
+// SAFETY: `Saturating` is a transparent wrapper.
+unsafe impl<T> NoPadding for Saturating<T> where T: NoPadding {}
+
+/// Returns `target_right` if `target_dependence` holds, `target_left` otherwise, blending their bytes.
+///
+/// Both values are read in full as `usize` words, the trailing bytes one by one, and every word of the output is
+/// combined through a mask from [`Filter::mask`], so no branch depends on `target_dependence` or on the values.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::bytes::select_bytes;
+///
+/// let target_left = [1u64, 2, 3];
+/// let target_right = [4u64, 5, 6];
+///
+/// assert_eq!(select_bytes(target_left, target_right, true), target_right);
+/// ```
+#[inline]
+pub fn select_bytes<T>(target_left: T, target_right: T, target_dependence: bool) -> T
+where
+    T: NoPadding,
+{
+    // SAFETY: `NoPadding` guarantees every byte of both values is initialized.
+    unsafe { select_bytes_unchecked(target_left, target_right, target_dependence) }
+}
+
+/// Same as [`select_bytes`], for any `Copy` type.
+///
+/// # Safety
+///
+/// Every byte of both values must be initialized, in particular `T` must not contain padding.
+#[inline]
+pub unsafe fn select_bytes_unchecked<T>(target_left: T, target_right: T, target_dependence: bool) -> T
+where
+    T: Copy,
+{
+    const WORD: usize = size_of::<usize>();
+
+    let select_mask: usize = Filter::new(target_dependence).mask();
+
+    let target_left = &target_left as *const T as *const u8;
+    let target_right = &target_right as *const T as *const u8;
+
+    let mut target_outcome = MaybeUninit::<T>::uninit();
+    let target_output = target_outcome.as_mut_ptr() as *mut u8;
+
+    let mut target_offset = 0;
+
+    while target_offset + WORD <= size_of::<T>() {
+        let target_left_word = ptr::read_unaligned(target_left.add(target_offset) as *const usize);
+        let target_right_word = ptr::read_unaligned(target_right.add(target_offset) as *const usize);
+
+        let target_word = target_left_word ^ ((target_left_word ^ target_right_word) & select_mask);
+
+        ptr::write_unaligned(target_output.add(target_offset) as *mut usize, target_word);
+
+        target_offset += WORD;
+    }
+
+    while target_offset < size_of::<T>() {
+        let target_left_byte = *target_left.add(target_offset);
+        let target_right_byte = *target_right.add(target_offset);
+
+        *target_output.add(target_offset) = target_left_byte ^ ((target_left_byte ^ target_right_byte) & select_mask as u8);
+
+        target_offset += 1;
+    }
+
+    // SAFETY: Every byte was written, and they are the bytes of one of both values.
+    target_outcome.assume_init()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{select_bytes, select_bytes_unchecked};
+
+    #[test]
+    fn select_bytes_matches_select() {
+        for target_dependence in [false, true] {
+            let target_pick = |target_left: [u8; 13], target_right: [u8; 13]| match target_dependence {
+                true => target_right,
+                false => target_left,
+            };
+
+            let target_left: [u8; 13] = core::array::from_fn(|target_index| target_index as u8);
+            let target_right: [u8; 13] = core::array::from_fn(|target_index| 0xF0 | target_index as u8);
+
+            assert_eq!(select_bytes(target_left, target_right, target_dependence), target_pick(target_left, target_right));
+            assert_eq!(select_bytes(-0.5f64, 3.25, target_dependence), [-0.5, 3.25][target_dependence as usize]);
+            assert_eq!(select_bytes('a', 'z', target_dependence), ['a', 'z'][target_dependence as usize]);
+            assert_eq!(select_bytes([1u128, 2], [3, 4], target_dependence), [[1, 2], [3, 4]][target_dependence as usize]);
+        }
+    }
+
+    #[test]
+    fn select_bytes_unchecked_struct() {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        #[repr(C)]
+        struct Key {
+            identifier: u64,
+            version: u32,
+            flags: [u8; 4],
+        }
+
+        let target_left = Key { identifier: 1, version: 2, flags: [3; 4] };
+        let target_right = Key { identifier: u64::MAX, version: 7, flags: [9; 4] };
+
+        // SAFETY: `Key` has no padding.
+        unsafe {
+            assert_eq!(select_bytes_unchecked(target_left, target_right, false), target_left);
+            assert_eq!(select_bytes_unchecked(target_left, target_right, true), target_right);
+        }
+    }
+}
@@ -0,0 +1,414 @@
+/*
+    linearity: A library for branchless programming
+    Copyright (C) 2024  advantageous-overtake
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Optional and fallible values whose validity is tracked without branching.
+//!
+//! See [`CtOption`] and [`CtResult`] for more information.
+
+use crate::Select;
+
+/// An optional value whose presence is a plain flag rather than a discriminant to branch on.
+///
+/// Every combinator evaluates all of its paths, even those whose outcome is discarded, and combines them through
+/// [`Select::select`], so validity can be threaded through a computation without early returns.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::CtOption;
+///
+/// let target_value = CtOption::new(7u32, true)
+///     .map(|target_value| target_value * 3)
+///     .and_then(|target_value| CtOption::new(target_value - 1, target_value > 20));
+///
+/// assert_eq!(target_value.unwrap_or(0), 20);
+/// assert_eq!(CtOption::new(7u32, false).map(|target_value| target_value * 3).unwrap_or(0), 0);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct CtOption<T> {
+    value: T,
+    is_some: bool,
+}
+
+impl<T> CtOption<T>
+where
+    T: Select,
+{
+    /// Creates a value that is present if `target_some` holds.
+    #[inline]
+    pub const fn new(target_value: T, target_some: bool) -> Self {
+        Self {
+            value: target_value,
+            is_some: target_some,
+        }
+    }
+
+    /// Creates a present value.
+    #[inline]
+    pub const fn some(target_value: T) -> Self {
+        Self::new(target_value, true)
+    }
+
+    /// Creates a missing value, the payload being stood in for by [`Default`].
+    #[inline]
+    pub fn none() -> Self
+    where
+        T: Default,
+    {
+        Self::new(T::default(), false)
+    }
+
+    /// Returns whether the value is present.
+    #[inline]
+    pub const fn is_some(&self) -> bool {
+        self.is_some
+    }
+
+    /// Returns whether the value is missing.
+    #[inline]
+    pub const fn is_none(&self) -> bool {
+        !self.is_some
+    }
+
+    /// Applies `target_function` to the payload, whether present or not.
+    #[inline]
+    pub fn map<U, F>(self, target_function: F) -> CtOption<U>
+    where
+        U: Select,
+        F: FnOnce(T) -> U,
+    {
+        CtOption::new(target_function(self.value), self.is_some)
+    }
+
+    /// Applies `target_function` to the payload, whether present or not, the outcome being present only if both are.
+    #[inline]
+    pub fn and_then<U, F>(self, target_function: F) -> CtOption<U>
+    where
+        U: Select,
+        F: FnOnce(T) -> CtOption<U>,
+    {
+        let target_outcome = target_function(self.value);
+
+        CtOption::new(target_outcome.value, self.is_some & target_outcome.is_some)
+    }
+
+    /// Returns the payload if present, `target_default` otherwise.
+    #[inline]
+    pub fn unwrap_or(self, target_default: T) -> T {
+        T::select(target_default, self.value, self.is_some)
+    }
+
+    /// Returns the payload if present, [`Default`] otherwise.
+    #[inline]
+    pub fn unwrap_or_default(self) -> T
+    where
+        T: Default,
+    {
+        self.unwrap_or(T::default())
+    }
+
+    /// Returns `self` if present, the outcome of `target_function` otherwise, which is evaluated either way.
+    #[inline]
+    pub fn or_else<F>(self, target_function: F) -> Self
+    where
+        F: FnOnce() -> Self,
+    {
+        Self::select(target_function(), self, self.is_some)
+    }
+
+    /// Converts into an [`Option`].
+    ///
+    /// This branches on the presence, so should only be done once it is no longer secret.
+    #[inline]
+    pub fn into_option(self) -> Option<T> {
+        self.is_some.then_some(self.value)
+    }
+}
+
+impl<T> Select for CtOption<T>
+where
+    T: Select,
+{
+    #[inline]
+    fn select(target_left: Self, target_right: Self, target_dependence: bool) -> Self {
+        Self::new(
+            T::select(target_left.value, target_right.value, target_dependence),
+            bool::select(target_left.is_some, target_right.is_some, target_dependence),
+        )
+    }
+}
+
+impl<T> From<CtOption<T>> for Option<T>
+where
+    T: Select,
+{
+    #[inline]
+    fn from(target_value: CtOption<T>) -> Self {
+        target_value.into_option()
+    }
+}
+
+/// A fallible value carrying both a success and an error payload, tracked by a plain flag.
+///
+/// Like [`CtOption`], every combinator evaluates all of its paths and combines them through [`Select::select`].
+///
+/// # Examples
+///
+/// ```
+/// use linearity::CtResult;
+///
+/// let target_value = CtResult::<u32, u8>::ok(40)
+///     .and_then(|target_value| CtResult::new(target_value + 2, 1, target_value < 100))
+///     .map_err(|target_error| target_error + 1);
+///
+/// assert_eq!(target_value.into_result(), Ok(42));
+/// assert_eq!(CtResult::<u32, u8>::err(3).map(|target_value| target_value + 2).into_result(), Err(3));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct CtResult<T, E> {
+    value: T,
+    error: E,
+    is_ok: bool,
+}
+
+impl<T, E> CtResult<T, E>
+where
+    T: Select,
+    E: Select,
+{
+    /// Creates a value that succeeded if `target_ok` holds, carrying both payloads.
+    #[inline]
+    pub const fn new(target_value: T, target_error: E, target_ok: bool) -> Self {
+        Self {
+            value: target_value,
+            error: target_error,
+            is_ok: target_ok,
+        }
+    }
+
+    /// Creates a success, the error being stood in for by [`Default`].
+    #[inline]
+    pub fn ok(target_value: T) -> Self
+    where
+        E: Default,
+    {
+        Self::new(target_value, E::default(), true)
+    }
+
+    /// Creates a failure, the success being stood in for by [`Default`].
+    #[inline]
+    pub fn err(target_error: E) -> Self
+    where
+        T: Default,
+    {
+        Self::new(T::default(), target_error, false)
+    }
+
+    /// Returns whether the value succeeded.
+    #[inline]
+    pub const fn is_ok(&self) -> bool {
+        self.is_ok
+    }
+
+    /// Returns whether the value failed.
+    #[inline]
+    pub const fn is_err(&self) -> bool {
+        !self.is_ok
+    }
+
+    /// Applies `target_function` to the success payload, whether succeeded or not.
+    #[inline]
+    pub fn map<U, F>(self, target_function: F) -> CtResult<U, E>
+    where
+        U: Select,
+        F: FnOnce(T) -> U,
+    {
+        CtResult::new(target_function(self.value), self.error, self.is_ok)
+    }
+
+    /// Applies `target_function` to the error payload, whether failed or not.
+    #[inline]
+    pub fn map_err<G, F>(self, target_function: F) -> CtResult<T, G>
+    where
+        G: Select,
+        F: FnOnce(E) -> G,
+    {
+        CtResult::new(self.value, target_function(self.error), self.is_ok)
+    }
+
+    /// Applies `target_function` to the success payload, whether succeeded or not.
+    ///
+    /// The outcome succeeds only if both do, otherwise carrying the first error.
+    #[inline]
+    pub fn and_then<U, F>(self, target_function: F) -> CtResult<U, E>
+    where
+        U: Select,
+        F: FnOnce(T) -> CtResult<U, E>,
+    {
+        let target_outcome = target_function(self.value);
+
+        CtResult::new(
+            target_outcome.value,
+            E::select(self.error, target_outcome.error, self.is_ok),
+            self.is_ok & target_outcome.is_ok,
+        )
+    }
+
+    /// Returns `self` if succeeded, the outcome of `target_function` on the error otherwise, which is evaluated
+    /// either way.
+    #[inline]
+    pub fn or_else<F>(self, target_function: F) -> Self
+    where
+        F: FnOnce(E) -> Self,
+    {
+        Self::select(target_function(self.error), self, self.is_ok)
+    }
+
+    /// Returns the success payload if succeeded, `target_default` otherwise.
+    #[inline]
+    pub fn unwrap_or(self, target_default: T) -> T {
+        T::select(target_default, self.value, self.is_ok)
+    }
+
+    /// Converts the success payload into a [`CtOption`].
+    #[inline]
+    pub const fn ok_value(self) -> CtOption<T> {
+        CtOption::new(self.value, self.is_ok)
+    }
+
+    /// Converts the error payload into a [`CtOption`].
+    #[inline]
+    pub const fn err_value(self) -> CtOption<E> {
+        CtOption::new(self.error, !self.is_ok)
+    }
+
+    /// Converts into a [`Result`].
+    ///
+    /// This branches on the outcome, so should only be done once it is no longer secret.
+    #[inline]
+    pub fn into_result(self) -> Result<T, E> {
+        match self.is_ok {
+            true => Ok(self.value),
+            false => Err(self.error),
+        }
+    }
+}
+
+impl<T, E> Select for CtResult<T, E>
+where
+    T: Select,
+    E: Select,
+{
+    #[inline]
+    fn select(target_left: Self, target_right: Self, target_dependence: bool) -> Self {
+        Self::new(
+            T::select(target_left.value, target_right.value, target_dependence),
+            E::select(target_left.error, target_right.error, target_dependence),
+            bool::select(target_left.is_ok, target_right.is_ok, target_dependence),
+        )
+    }
+}
+
+impl<T, E> From<CtResult<T, E>> for Result<T, E>
+where
+    T: Select,
+    E: Select,
+{
+    #[inline]
+    fn from(target_value: CtResult<T, E>) -> Self {
+        target_value.into_result()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CtOption, CtResult};
+
+    #[test]
+    fn ct_option_combinators() {
+        for target_some in [false, true] {
+            let mut target_calls = 0;
+
+            let target_value = CtOption::new(5i32, target_some)
+                .map(|target_value| {
+                    target_calls += 1;
+
+                    target_value * 2
+                })
+                .or_else(|| CtOption::some(-1));
+
+            assert_eq!(target_calls, 1);
+            assert!(target_value.is_some());
+            assert_eq!(
+                target_value.into_option(),
+                Some(if target_some { 10 } else { -1 })
+            );
+
+            let target_value = CtOption::new(5i32, target_some)
+                .and_then(|target_value| CtOption::new(target_value, false));
+
+            assert!(target_value.is_none());
+            assert_eq!(target_value.unwrap_or_default(), 0);
+            assert_eq!(
+                CtOption::<i32>::none()
+                    .or_else(|| CtOption::new(3, target_some))
+                    .into_option(),
+                target_some.then_some(3)
+            );
+        }
+    }
+
+    #[test]
+    fn ct_result_combinators() {
+        for target_ok in [false, true] {
+            let target_value = CtResult::<u16, u8>::new(7, 2, target_ok);
+
+            assert_eq!(
+                target_value
+                    .map(|target_value| target_value + 1)
+                    .into_result(),
+                if target_ok { Ok(8) } else { Err(2) }
+            );
+            assert_eq!(
+                target_value
+                    .map_err(|target_error| target_error * 3)
+                    .into_result(),
+                if target_ok { Ok(7) } else { Err(6) }
+            );
+            assert_eq!(
+                target_value
+                    .or_else(|target_error| CtResult::ok(target_error as u16))
+                    .into_result(),
+                Ok(if target_ok { 7 } else { 2 })
+            );
+            assert_eq!(
+                target_value.and_then(|_| CtResult::<u16, u8>::err(9)).into_result(),
+                Err(if target_ok { 9 } else { 2 })
+            );
+            assert_eq!(
+                target_value.ok_value().into_option(),
+                target_ok.then_some(7)
+            );
+            assert_eq!(
+                target_value.err_value().into_option(),
+                (!target_ok).then_some(2)
+            );
+            assert_eq!(target_value.unwrap_or(0), if target_ok { 7 } else { 0 });
+        }
+    }
+}
@@ -0,0 +1,158 @@
+/*
+    linearity: A library for branchless programming
+    Copyright (C) 2024  advantageous-overtake
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Audits that selections compile without conditional branches.
+//!
+//! Every audited operation is wrapped in an unmangled, non-inlined function per primitive type, and the test binary
+//! itself is disassembled through `objdump`, failing on any conditional jump found in a wrapper. The audit is only
+//! meaningful with optimizations enabled, so it is ignored otherwise and run through `cargo test --release`.
+
+use std::{collections::HashMap, env, ops::Range, process::Command};
+
+use crate::{primitive::primitive_list, Filter, Linearity};
+
+/// Declares the audited wrappers for primitive types, along with the list of their symbols.
+macro_rules! impl_wrappers {
+    (
+        $(
+            $target_type:ident
+        )+
+    ) => {
+        paste::item! {
+            $(
+                #[no_mangle]
+                #[inline(never)]
+                fn [< linearity_ct_audit_select_ $target_type >](target_left: $target_type, target_right: $target_type, target_dependence: bool) -> $target_type {
+                    target_left.select(target_right, target_dependence)
+                }
+
+                #[no_mangle]
+                #[inline(never)]
+                fn [< linearity_ct_audit_filter_ $target_type >](target_value: $target_type, target_dependence: bool) -> $target_type {
+                    target_value.filter(Filter::new(target_dependence))
+                }
+
+                #[no_mangle]
+                #[inline(never)]
+                fn [< linearity_ct_audit_mask_ $target_type >](target_dependence: bool) -> $target_type {
+                    Filter::new(target_dependence).mask()
+                }
+
+                #[no_mangle]
+                #[inline(never)]
+                fn [< linearity_ct_audit_select_sign_ $target_type >](target_value: $target_type) -> $target_type {
+                    target_value.select_sign(1, 2, 3)
+                }
+
+                #[no_mangle]
+                #[inline(never)]
+                fn [< linearity_ct_audit_select_index_ $target_type >](target_values: [$target_type; 4], target_index: usize) -> $target_type {
+                    Linearity::select_index(target_values, target_index)
+                }
+            )+
+
+            /// Keeps every wrapper alive, returning its symbol along with its address.
+            fn wrappers() -> Vec<(&'static str, usize)> {
+                vec![
+                    $(
+                        (stringify!([< linearity_ct_audit_select_ $target_type >]), core::hint::black_box([< linearity_ct_audit_select_ $target_type >] as *const () as usize)),
+                        (stringify!([< linearity_ct_audit_filter_ $target_type >]), core::hint::black_box([< linearity_ct_audit_filter_ $target_type >] as *const () as usize)),
+                        (stringify!([< linearity_ct_audit_mask_ $target_type >]), core::hint::black_box([< linearity_ct_audit_mask_ $target_type >] as *const () as usize)),
+                        (stringify!([< linearity_ct_audit_select_sign_ $target_type >]), core::hint::black_box([< linearity_ct_audit_select_sign_ $target_type >] as *const () as usize)),
+                        (stringify!([< linearity_ct_audit_select_index_ $target_type >]), core::hint::black_box([< linearity_ct_audit_select_index_ $target_type >] as *const () as usize)),
+                    )+
+                ]
+            }
+        }
+    };
+}
+
+primitive_list!(impl_wrappers);
+
+/// Returns the address range of every symbol defined in `target_binary`, through `nm`.
+fn symbols(target_binary: &str) -> HashMap<String, Range<u64>> {
+    let target_output = Command::new("nm")
+        .args(["-S", "--defined-only", target_binary])
+        .output()
+        .expect("`nm` must be available");
+
+    assert!(target_output.status.success(), "`nm` failed");
+
+    String::from_utf8_lossy(&target_output.stdout)
+        .lines()
+        .filter_map(|target_line| {
+            let mut target_fields = target_line.split_whitespace();
+
+            let target_address = u64::from_str_radix(target_fields.next()?, 16).ok()?;
+            let target_size = u64::from_str_radix(target_fields.next()?, 16).ok()?;
+            let target_symbol = target_fields.nth(1)?;
+
+            Some((target_symbol.to_owned(), target_address..target_address + target_size))
+        })
+        .collect()
+}
+
+/// Returns the conditional jumps within `target_range` of `target_binary`, through `objdump`.
+fn conditional_jumps(target_binary: &str, target_range: &Range<u64>) -> Vec<String> {
+    let target_output = Command::new("objdump")
+        .args([
+            "-d",
+            "--no-show-raw-insn",
+            &format!("--start-address={:#x}", target_range.start),
+            &format!("--stop-address={:#x}", target_range.end),
+            target_binary,
+        ])
+        .output()
+        .expect("`objdump` must be available");
+
+    assert!(target_output.status.success(), "`objdump` failed");
+
+    String::from_utf8_lossy(&target_output.stdout)
+        .lines()
+        .filter_map(|target_line| target_line.split('\t').nth(1))
+        .filter(|target_instruction| {
+            let target_mnemonic = target_instruction.split_whitespace().next().unwrap_or_default();
+
+            (target_mnemonic.starts_with('j') && target_mnemonic != "jmp") || target_mnemonic.starts_with("loop")
+        })
+        .map(str::to_owned)
+        .collect()
+}
+
+#[test]
+#[cfg_attr(debug_assertions, ignore = "requires optimizations, run through `cargo test --release`")]
+fn ct_audit_no_conditional_branches() {
+    let target_binary = env::current_exe().expect("the test binary must be locatable");
+    let target_binary = target_binary.to_str().expect("the test binary path must be valid UTF-8");
+
+    let target_symbols = symbols(target_binary);
+
+    let target_failures: Vec<_> = wrappers()
+        .into_iter()
+        .map(|(target_symbol, _)| {
+            let target_range = target_symbols
+                .get(target_symbol)
+                .unwrap_or_else(|| panic!("missing symbol {target_symbol}"));
+
+            (target_symbol, conditional_jumps(target_binary, target_range))
+        })
+        .filter(|(_, target_jumps)| !target_jumps.is_empty())
+        .collect();
+
+    assert!(target_failures.is_empty(), "conditional branches found: {target_failures:#?}");
+}
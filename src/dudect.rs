@@ -0,0 +1,343 @@
+/*
+    linearity: A library for branchless programming
+    Copyright (C) 2024  advantageous-overtake
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Timing leakage detection, after [dudect](https://github.com/oreparaz/dudect).
+//!
+//! A function is run many times on inputs drawn from two classes, usually a fixed secret against random ones, its
+//! duration being measured in cycles through [`_rdtsc`]. Welch's t-test is then applied to both populations: a
+//! statistic beyond [`THRESHOLD`] in magnitude indicates that the duration depends on the class, hence on the secret.
+//!
+//! Measurements are noisy, so the ready-made harnesses are best run on an idle machine, in release mode.
+
+use core::{
+    arch::x86_64::{_mm_lfence, _rdtsc},
+    hint,
+};
+
+use crate::{
+    primitive::{
+        cast::Cast,
+        op::binary::{BitAnd, BitXor},
+        Primitive,
+    },
+    random::Xorshift,
+    slice::{branchless_lower_bound, select_index as slice_select_index},
+    sort::sort_network as network_sort,
+    Filter, Linearity,
+};
+
+/// The magnitude of the t statistic beyond which a timing leak is reported.
+pub const THRESHOLD: f64 = 4.5;
+
+/// The number of measurements, taken ahead of the samples, from which the cropping thresholds are derived.
+const CALIBRATION: usize = 1_000;
+
+/// The number of cropping thresholds, the `i`-th keeping the durations up to the `1 - 2^-i` percentile.
+const CROPS: usize = 10;
+
+/// Welch's t-test over two populations, accumulated online.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WelchTest {
+    count: [f64; 2],
+    mean: [f64; 2],
+    variance: [f64; 2],
+}
+
+impl WelchTest {
+    /// Creates an empty test.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            count: [0.0; 2],
+            mean: [0.0; 2],
+            variance: [0.0; 2],
+        }
+    }
+
+    /// Adds `target_value` to the population of `target_class`.
+    #[inline]
+    pub fn push(&mut self, target_class: bool, target_value: f64) {
+        let target_class = target_class as usize;
+
+        // NOTE: Welford's algorithm, `variance` holding the sum of squared deviations until divided.
+        self.count[target_class] += 1.0;
+
+        let target_delta = target_value - self.mean[target_class];
+
+        self.mean[target_class] += target_delta / self.count[target_class];
+        self.variance[target_class] += target_delta * (target_value - self.mean[target_class]);
+    }
+
+    /// Returns the number of values in the population of `target_class`.
+    #[inline]
+    pub fn count(&self, target_class: bool) -> u64 {
+        self.count[target_class as usize] as u64
+    }
+
+    /// Returns the t statistic, zero if either population has fewer than two values.
+    pub fn t_value(&self) -> f64 {
+        if self.count[0] < 2.0 || self.count[1] < 2.0 {
+            return 0.0;
+        }
+
+        let target_error = self.variance[0] / (self.count[0] - 1.0) / self.count[0]
+            + self.variance[1] / (self.count[1] - 1.0) / self.count[1];
+
+        match target_error > 0.0 {
+            true => (self.mean[0] - self.mean[1]) / sqrt(target_error),
+            false => 0.0,
+        }
+    }
+
+    /// Returns whether the t statistic exceeds [`THRESHOLD`] in magnitude.
+    #[inline]
+    pub fn leaks(&self) -> bool {
+        self.t_value().abs() > THRESHOLD
+    }
+}
+
+/// Returns the square root of a positive `target_value`, through Newton's method.
+fn sqrt(target_value: f64) -> f64 {
+    let mut target_root = f64::from_bits((target_value.to_bits() >> 1) + (0x3FF << 51));
+
+    for _ in 0..6 {
+        target_root = 0.5 * (target_root + target_value / target_root);
+    }
+
+    target_root
+}
+
+/// Returns the current cycle count, serialized against surrounding instructions.
+#[inline(always)]
+fn cycles() -> u64 {
+    // SAFETY: `lfence` and `rdtsc` are available on every `x86_64` processor.
+    unsafe {
+        _mm_lfence();
+
+        let target_cycles = _rdtsc();
+
+        _mm_lfence();
+
+        target_cycles
+    }
+}
+
+/// Measures `target_function` over `target_samples` inputs, returning the accumulated test.
+///
+/// For every sample a class is drawn at random, and an input is built by `target_generate` from the class and a random
+/// word, outside of the measurement. Conventionally, the `false` class uses a fixed input and the `true` class a random
+/// one.
+///
+/// As in dudect, the durations are cropped to drop the outliers that interrupts and frequency changes leave in the upper
+/// tail: a first batch of measurements, which is not sampled, sets thresholds at increasing percentiles. A test is
+/// accumulated for every threshold, and one without cropping, the test with the largest statistic in magnitude being
+/// returned.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::dudect::measure;
+///
+/// let target_test = measure(10_000, |_, target_random| target_random, |target_value: u64| target_value.rotate_left(7));
+///
+/// assert!(target_test.count(false) + target_test.count(true) <= 10_000);
+/// ```
+pub fn measure<I, O, G, F>(target_samples: usize, mut target_generate: G, mut target_function: F) -> WelchTest
+where
+    G: FnMut(bool, u64) -> I,
+    F: FnMut(I) -> O,
+{
+    let mut target_random = Xorshift::new(0x9E37_79B9_7F4A_7C15);
+
+    let mut target_run = || {
+        let target_class = target_random.next() & 1 == 1;
+        let target_input = hint::black_box(target_generate(target_class, target_random.next()));
+
+        let target_start = cycles();
+
+        hint::black_box(target_function(target_input));
+
+        let target_end = cycles();
+
+        (target_class, target_end.wrapping_sub(target_start))
+    };
+
+    let mut target_calibration = [0u64; CALIBRATION];
+
+    for target_duration in &mut target_calibration {
+        *target_duration = target_run().1;
+    }
+
+    target_calibration.sort_unstable();
+
+    let mut target_fraction = 1.0;
+    let target_thresholds: [u64; CROPS] = core::array::from_fn(|_| {
+        target_fraction *= 0.5;
+
+        target_calibration[((1.0 - target_fraction) * CALIBRATION as f64) as usize]
+    });
+
+    // NOTE: The last test is left uncropped.
+    let mut target_tests = [WelchTest::new(); CROPS + 1];
+
+    for _ in 0..target_samples {
+        let (target_class, target_duration) = target_run();
+
+        for (target_test, &target_threshold) in target_tests.iter_mut().zip(target_thresholds.iter().chain([&u64::MAX])) {
+            if target_duration <= target_threshold {
+                target_test.push(target_class, target_duration as f64);
+            }
+        }
+    }
+
+    target_tests
+        .into_iter()
+        .max_by(|target_left, target_right| target_left.t_value().abs().total_cmp(&target_right.t_value().abs()))
+        .unwrap_or_default()
+}
+
+/// Measures [`Linearity::select`] on `T`, the condition being the secret.
+pub fn select<T>(target_samples: usize) -> WelchTest
+where
+    T: Primitive,
+    T: BitXor<Operand = T, Output = T> + BitAnd<Operand = T, Output = T>,
+
+    T: Cast<T>,
+    i8: Cast<T>,
+    u64: Cast<T>,
+{
+    measure(
+        target_samples,
+        |target_class, target_random| {
+            let target_left: T = target_random.cast();
+            let target_right: T = target_random.rotate_left(32).cast();
+
+            (target_left, target_right, target_class & (target_random >> 63 == 1))
+        },
+        |(target_left, target_right, target_dependence)| target_left.select(target_right, target_dependence),
+    )
+}
+
+/// Measures [`Filter::mask`] on `T`, the filter being the secret.
+pub fn mask<T>(target_samples: usize) -> WelchTest
+where
+    T: Primitive,
+    i8: Cast<T>,
+{
+    measure(
+        target_samples,
+        |target_class, target_random| Filter::new(target_class & (target_random & 1 == 1)),
+        |target_filter| target_filter.mask::<T>(),
+    )
+}
+
+/// Measures [`slice::select_index`](crate::slice::select_index) over 64 elements, the index being the secret.
+pub fn select_index(target_samples: usize) -> WelchTest {
+    let target_values: [u64; 64] = core::array::from_fn(|target_index| target_index as u64 * 0x0101_0101);
+
+    measure(
+        target_samples,
+        |target_class, target_random| (target_random as usize % 64).filter::<usize>(Filter::new(target_class)),
+        |target_index| slice_select_index(&target_values, target_index),
+    )
+}
+
+/// Measures [`branchless_lower_bound`] over 64 elements, the key being the secret.
+pub fn lower_bound(target_samples: usize) -> WelchTest {
+    let target_values: [u64; 64] = core::array::from_fn(|target_index| target_index as u64 * 3);
+
+    measure(
+        target_samples,
+        |target_class, target_random| (target_random % 200).filter::<u64>(Filter::new(target_class)),
+        |target_key| branchless_lower_bound(&target_values, &target_key),
+    )
+}
+
+/// Measures [`sort_network`](crate::sort::sort_network) over 16 elements, the contents being the secret.
+pub fn sort_network(target_samples: usize) -> WelchTest {
+    measure(
+        target_samples,
+        |target_class, target_random| {
+            let mut target_state = Xorshift::new(target_random | 1);
+
+            core::array::from_fn::<u32, 16, _>(|target_index| {
+                (target_state.next() as u32).select(target_index as u32, !target_class)
+            })
+        },
+        |mut target_array| {
+            network_sort(&mut target_array);
+
+            target_array
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use core::hint;
+
+    use super::{measure, WelchTest};
+
+    #[test]
+    fn welch_test_statistic() {
+        let mut target_test = WelchTest::new();
+
+        for target_index in 0..1000 {
+            target_test.push(false, (target_index % 10) as f64);
+            target_test.push(true, (target_index % 10) as f64);
+        }
+
+        assert_eq!(target_test.t_value(), 0.0);
+        assert!(!target_test.leaks());
+
+        for target_index in 0..1000 {
+            target_test.push(true, (target_index % 10 + 5) as f64);
+        }
+
+        assert!(target_test.leaks());
+        assert!(target_test.t_value() < 0.0);
+    }
+
+    #[test]
+    fn measure_detects_leak() {
+        let target_test = measure(
+            2_000,
+            |target_class, _| target_class as u64 * 2_000,
+            |target_count| (0..target_count).fold(0u64, |target_value, target_index| hint::black_box(target_value ^ target_index)),
+        );
+
+        assert!(target_test.leaks());
+    }
+
+    /// Runs the ready-made harnesses, which is only meaningful in release mode on an idle machine.
+    #[test]
+    #[ignore = "timing-sensitive, run through `cargo test --release --features dudect -- --ignored`"]
+    fn harnesses_constant_time() {
+        const SAMPLES: usize = 1_000_000;
+
+        for (target_name, target_test) in [
+            ("select", super::select::<u64>(SAMPLES)),
+            ("mask", super::mask::<u64>(SAMPLES)),
+            ("select_index", super::select_index(SAMPLES)),
+            ("lower_bound", super::lower_bound(SAMPLES)),
+            ("sort_network", super::sort_network(SAMPLES)),
+        ] {
+            assert!(!target_test.leaks(), "{target_name} leaks, t = {}", target_test.t_value());
+        }
+    }
+}
@@ -0,0 +1,227 @@
+/*
+    linearity: A library for branchless programming
+    Copyright (C) 2024  advantageous-overtake
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! A binary max-heap over caller-provided storage.
+//!
+//! See [`BranchlessHeap`] for more information.
+
+use crate::{sort::compare_exchange, Linearity, Select};
+
+/// A binary max-heap whose sifting never branches on the stored values.
+///
+/// Sifting up compares against every ancestor up to the root, sifting down follows the greater child down to a leaf.
+/// Every step is a conditional swap, so the number of steps depends on the length alone, and once the heap property is
+/// restored the remaining swaps leave the values in place.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::heap::BranchlessHeap;
+///
+/// let mut target_storage = [0u32; 8];
+/// let mut target_heap = BranchlessHeap::new(&mut target_storage);
+///
+/// for target_value in [4, 9, 1, 7] {
+///     target_heap.push(target_value).unwrap();
+/// }
+///
+/// assert_eq!(target_heap.peek(), Some(&9));
+/// assert_eq!(target_heap.replace_top(3), Some(9));
+/// assert_eq!(target_heap.pop(), Some(7));
+/// assert_eq!(target_heap.pop(), Some(4));
+/// ```
+#[derive(Debug)]
+pub struct BranchlessHeap<'a, T> {
+    target_storage: &'a mut [T],
+    target_length: usize,
+}
+
+impl<'a, T> BranchlessHeap<'a, T>
+where
+    T: Select + Ord,
+{
+    /// Creates an empty heap whose capacity is the length of `target_storage`.
+    #[inline]
+    pub fn new(target_storage: &'a mut [T]) -> Self {
+        Self {
+            target_storage,
+            target_length: 0,
+        }
+    }
+
+    /// Returns the number of elements in the heap.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.target_length
+    }
+
+    /// Returns `true` if the heap holds no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.target_length == 0
+    }
+
+    /// Returns the number of elements the heap can hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.target_storage.len()
+    }
+
+    /// Removes every element from the heap.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.target_length = 0;
+    }
+
+    /// Returns the elements of the heap, in heap order.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.target_storage[..self.target_length]
+    }
+
+    /// Returns the greatest element, or `None` if the heap is empty.
+    #[inline]
+    pub fn peek(&self) -> Option<&T> {
+        self.as_slice().first()
+    }
+
+    /// Pushes `target_value` onto the heap.
+    ///
+    /// # Errors
+    ///
+    /// Returns `target_value` back if the heap is full.
+    #[inline]
+    pub fn push(&mut self, target_value: T) -> Result<(), T> {
+        if self.target_length == self.capacity() {
+            return Err(target_value);
+        }
+
+        self.target_storage[self.target_length] = target_value;
+        self.target_length += 1;
+
+        self.sift_up(self.target_length - 1);
+
+        Ok(())
+    }
+
+    /// Removes and returns the greatest element, or `None` if the heap is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        let &target_top = self.peek()?;
+
+        self.target_length -= 1;
+        self.target_storage[0] = self.target_storage[self.target_length];
+
+        self.sift_down();
+
+        Some(target_top)
+    }
+
+    /// Replaces the greatest element with `target_value`, returning the former.
+    ///
+    /// This is cheaper than a [`pop`](Self::pop) followed by a [`push`](Self::push). Returns `None`, without inserting
+    /// `target_value`, if the heap is empty.
+    #[inline]
+    pub fn replace_top(&mut self, target_value: T) -> Option<T> {
+        let &target_top = self.peek()?;
+
+        self.target_storage[0] = target_value;
+
+        self.sift_down();
+
+        Some(target_top)
+    }
+
+    /// Moves the element at `target_index` up towards the root.
+    #[inline]
+    fn sift_up(&mut self, mut target_index: usize) {
+        while target_index > 0 {
+            let target_parent = (target_index - 1) / 2;
+
+            compare_exchange(self.target_storage, target_index, target_parent);
+
+            target_index = target_parent;
+        }
+    }
+
+    /// Moves the root down towards the leaves.
+    #[inline]
+    fn sift_down(&mut self) {
+        let target_storage = &mut self.target_storage[..self.target_length];
+
+        let mut target_index = 0;
+
+        loop {
+            let target_left = 2 * target_index + 1;
+            let target_right = target_left + 1;
+
+            let target_child = match target_right < target_storage.len() {
+                // Whether the right child exists depends on the length alone.
+                true => target_left.select::<usize>(target_right, target_storage[target_right] > target_storage[target_left]),
+                false if target_left < target_storage.len() => target_left,
+                false => break,
+            };
+
+            compare_exchange(target_storage, target_child, target_index);
+
+            target_index = target_child;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::random::Xorshift;
+
+    use super::BranchlessHeap;
+
+    #[test]
+    fn heap_matches_binary_heap() {
+        use std::collections::BinaryHeap;
+
+        let mut target_random = Xorshift::new(0xABCD_EF01_2345_6789);
+
+        let mut target_storage = [0i32; 64];
+        let mut target_value = BranchlessHeap::new(&mut target_storage);
+        let mut target_expect = BinaryHeap::new();
+
+        for _ in 0..10_000 {
+            let target_element = target_random.next() as i32 % 100;
+
+            match target_random.next() % 3 {
+                0 => assert_eq!(target_value.pop(), target_expect.pop()),
+                1 if !target_expect.is_empty() => {
+                    let target_top = target_expect.pop();
+
+                    target_expect.push(target_element);
+
+                    assert_eq!(target_value.replace_top(target_element), target_top);
+                }
+                _ if target_expect.len() < 64 => {
+                    target_expect.push(target_element);
+
+                    assert_eq!(target_value.push(target_element), Ok(()));
+                }
+                _ => assert_eq!(target_value.push(target_element), Err(target_element)),
+            }
+
+            assert_eq!(target_value.len(), target_expect.len());
+            assert_eq!(target_value.peek(), target_expect.peek());
+        }
+    }
+}
@@ -20,17 +20,46 @@
 #![deny(missing_docs)]
 #![doc = include_str!("../README.md")]
 
-pub use blanket::{PointerExt, PointerMutExt};
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+// Lets derive expansions refer to `::linearity` within this crate's tests.
+#[cfg(test)]
+extern crate self as linearity;
+
+pub use blanket::{FatPointerExt, PointerExt, PointerMutExt, RefExt};
+pub use ct::{CtOption, CtResult};
 pub use filter::Filter;
+pub use secret::Secret;
+pub use select::Select;
+pub use zeroize::{secure_zero, Zeroize, Zeroizing};
+
+#[cfg(feature = "derive")]
+pub use linearity_derive::Select;
 use primitive::{
     cast::Cast,
-    op::binary::{BitAnd, BitXor},
+    op::binary::{BitAnd, BitOr, BitXor},
     Primitive,
 };
 
 mod blanket;
+pub mod bytes;
+mod ct;
+#[cfg(all(test, target_arch = "x86_64"))]
+mod ct_audit;
+#[cfg(all(feature = "dudect", target_arch = "x86_64"))]
+pub mod dudect;
 mod filter;
+pub mod heap;
+mod macros;
 pub mod primitive;
+#[cfg(any(test, all(feature = "dudect", target_arch = "x86_64")))]
+mod random;
+mod secret;
+mod select;
+pub mod slice;
+pub mod sort;
+mod zeroize;
 
 pub mod prelude;
 
@@ -113,14 +142,75 @@ pub trait Linearity: Primitive {
 
         target_operand.and(select_mask)
     }
+
+    /// Returns the element of `target_values` at `target_index`, reading every element.
+    ///
+    /// Every candidate is masked by whether its position matches `target_index`, and the results are OR-ed together.
+    /// If `target_index` is out of bounds, no candidate matches and zero is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use linearity::Linearity;
+    ///
+    /// let target_values = [10, 20, 30, 40];
+    ///
+    /// assert_eq!(Linearity::select_index(target_values, 2), 30);
+    /// assert_eq!(Linearity::select_index(target_values, 4), 0);
+    /// ```
+    #[inline]
+    fn select_index<const N: usize>(target_values: [Self; N], target_index: usize) -> Self
+    where
+        Self: BitOr<Operand = Self, Output = Self> + BitAnd<Operand = Self, Output = Self>,
+
+        i8: Cast<Self>,
+    {
+        slice::select_index(&target_values, target_index)
+    }
+
+    /// Performs a three-way selection based on the sign of `self`.
+    ///
+    /// Returns `target_negative`, `target_zero` or `target_positive` if `self` is less than, equal to or greater than
+    /// zero respectively.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use linearity::Linearity;
+    ///
+    /// let value = -7;
+    ///
+    /// assert_eq!(value.select_sign(-1i8, 0, 1), -1);
+    /// assert_eq!(0u32.select_sign(10u8, 20, 30), 20);
+    /// ```
+    #[inline]
+    fn select_sign<T>(self, target_negative: T, target_zero: T, target_positive: T) -> T
+    where
+        T: Primitive,
+        T: BitXor<Operand = T, Output = T> + BitAnd<Operand = T, Output = T>,
+
+        T: Cast<T>,
+        i8: Cast<T>,
+
+        Self: Ord,
+        i8: Cast<Self>,
+    {
+        let target_origin: Self = 0i8.cast();
+
+        target_zero
+            .select(target_negative, self < target_origin)
+            .select(target_positive, self > target_origin)
+    }
 }
 
 impl<T> Linearity for T where T: Primitive {}
 
 #[cfg(test)]
 mod tests {
-    use crate::primitive::primitive_list;
-    use const_random::const_random as random;
+    use crate::primitive::{
+        check::{cases, check, pairs},
+        primitive_list,
+    };
     use paste::item;
 
     macro_rules! impl_select {
@@ -135,21 +225,18 @@ mod tests {
                     fn [< impl_ select_ $target_ty:snake >] () {
                         use super::Linearity;
 
-                        let target_left: $target_ty = random!($target_ty);
-                        let target_right: $target_ty = random!($target_ty);
-
-                        let target_dependence: bool = random!(u8) == random!(u8);
-
-
-                        let target_expect: $target_ty = if target_dependence {
-                            target_right
-                        } else {
-                            target_left
-                        };
+                        check(pairs::<$target_ty, $target_ty>(), |(target_left, target_right)| {
+                            let target_left_value: $target_ty = target_left.select(target_right, false);
+                            let target_right_value: $target_ty = target_left.select(target_right, true);
 
-                        let target_value: $target_ty = target_left.select(target_right, target_dependence);
+                            target_left_value == target_left && target_right_value == target_right
+                        });
 
-                        assert_eq!(target_expect, target_value);
+                        // NOTE: The left operand is cast to the output type first.
+                        check(pairs::<$target_ty, i8>(), |(target_left, target_right)| {
+                            target_left.select(target_right, false) == target_left as i8
+                                && target_left.select(target_right, true) == target_right
+                        });
                     }
                 )+
             );
@@ -169,17 +256,90 @@ mod tests {
                         use super::Linearity;
                         use super::filter::Filter;
 
-                        let target_left: $target_ty = random!($target_ty);
-                        let target_dependence: Filter = Filter::new(random!(u8) == random!(u8));
+                        check(cases::<$target_ty>(), |target_left| {
+                            let target_opaque: $target_ty = target_left.filter(Filter::Opaque);
+                            let target_transparent: $target_ty = target_left.filter(Filter::Transparent);
+                            let target_narrow: u8 = target_left.filter(Filter::Transparent);
 
-                        let target_expect: $target_ty = match target_dependence {
-                            Filter::Opaque => 0,
-                            Filter::Transparent => target_left,
-                        };
+                            target_opaque == 0 && target_transparent == target_left && target_narrow == target_left as u8
+                        });
+                    }
+                )+
+            );
+        };
+    }
 
-                        let target_value: $target_ty = target_left.filter(target_dependence);
+    macro_rules! impl_mask {
+        (
+            $(
+                $target_ty:ident
+            )+
+        ) => {
+            item!(
+                $(
+                    #[test]
+                    fn [< impl_ mask_ $target_ty:snake >] () {
+                        use super::filter::Filter;
+
+                        assert_eq!(Filter::Opaque.mask::<$target_ty>(), 0);
+                        assert_eq!(Filter::Transparent.mask::<$target_ty>(), !0);
 
-                        assert_eq!(target_expect, target_value);
+                        assert_eq!(Filter::condition::<$target_ty>(false), 0);
+                        assert_eq!(Filter::condition::<$target_ty>(true), !0);
+                    }
+                )+
+            );
+        };
+    }
+
+    macro_rules! impl_select_index {
+        (
+            $(
+                $target_ty:ident
+            )+
+        ) => {
+            item!(
+                $(
+                    #[test]
+                    fn [< impl_ select_index_ $target_ty:snake >] () {
+                        use super::Linearity;
+
+                        check(pairs::<$target_ty, $target_ty>(), |(target_left, target_right)| {
+                            let target_values: [$target_ty; 4] = [target_left, target_right, !target_left, target_right ^ target_left];
+
+                            (0..6).all(|target_index| {
+                                let target_expect: $target_ty = target_values.get(target_index).copied().unwrap_or(0);
+
+                                Linearity::select_index(target_values, target_index) == target_expect
+                            })
+                        });
+                    }
+                )+
+            );
+        };
+    }
+
+    macro_rules! impl_select_sign {
+        (
+            $(
+                $target_ty:ident
+            )+
+        ) => {
+            item!(
+                $(
+                    #[test]
+                    fn [< impl_ select_sign_ $target_ty:snake >] () {
+                        use super::Linearity;
+
+                        check(cases::<$target_ty>(), |target_left| {
+                            let target_expect: i8 = match target_left.cmp(&0) {
+                                core::cmp::Ordering::Less => -1,
+                                core::cmp::Ordering::Equal => 0,
+                                core::cmp::Ordering::Greater => 1,
+                            };
+
+                            target_left.select_sign(-1i8, 0, 1) == target_expect
+                        });
                     }
                 )+
             );
@@ -188,5 +348,11 @@ mod tests {
 
     primitive_list!(impl_select);
 
+    primitive_list!(impl_select_index);
+
+    primitive_list!(impl_select_sign);
+
     primitive_list!(impl_filter);
+
+    primitive_list!(impl_mask);
 }
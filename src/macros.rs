@@ -0,0 +1,211 @@
+/*
+    linearity: A library for branchless programming
+    Copyright (C) 2024  advantageous-overtake
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Declarative macros lowering conditional expressions to branchless selections.
+//!
+//! See [`branchless!`](crate::branchless) for more information.
+
+/// Lowers an `if`/`else` chain or a `match` over integer literals into [`Select::select`](crate::Select::select)
+/// chains.
+///
+/// Every arm and every condition is evaluated eagerly, the conditions are turned into masks and the results combined,
+/// so the expansion carries no branch on the data. Arms must therefore be free of side effects one relies upon being
+/// skipped, and cheap enough to always compute.
+///
+/// * `if` chains must end with an `else` arm, arms may nest further `if`/`match` expressions.
+/// * `match` arms are integer literal patterns, possibly joined with `|`, and must end with a `_` arm. The first
+///   matching arm wins, as for a regular `match`.
+/// * As for a regular `if` or `match`, every arm must have the same type, which must implement
+///   [`Select`](crate::Select).
+///
+/// # Examples
+///
+/// ```
+/// use linearity::branchless;
+///
+/// let (a, b) = (3i32, 8i32);
+///
+/// let minimum = branchless! { if a < b { a } else { b } };
+///
+/// let clamped = branchless! {
+///     if b > 5 { 5 } else if b < 0 { 0 } else { b }
+/// };
+///
+/// let scaled = branchless! {
+///     match a {
+///         0 => 10u8,
+///         1 | 2 => 20,
+///         3 => 30,
+///         _ => 0,
+///     }
+/// };
+///
+/// assert_eq!(minimum, 3);
+/// assert_eq!(clamped, 5);
+/// assert_eq!(scaled, 30);
+/// ```
+///
+/// Arms of different types are rejected rather than cast:
+///
+/// ```compile_fail
+/// use linearity::branchless;
+///
+/// let condition = false;
+///
+/// let _ = branchless! { if condition { 5u8 } else { 300u16 } };
+/// ```
+///
+/// ```compile_fail
+/// use linearity::branchless;
+///
+/// let scrutinee = 3i32;
+///
+/// let _ = branchless! { match scrutinee { 0 => 5u8, _ => 300u16 } };
+/// ```
+#[macro_export]
+macro_rules! branchless {
+    // `if` chains, the condition is gathered token by token up to the block followed by `else`.
+    (if $($target_rest:tt)+) => {
+        $crate::branchless!(@condition [] $($target_rest)+)
+    };
+    (@condition [$($target_condition:tt)+] { $($target_then:tt)* } else if $($target_rest:tt)+) => {
+        $crate::Select::select(
+            $crate::branchless!(if $($target_rest)+),
+            $crate::branchless!($($target_then)*),
+            ($($target_condition)+),
+        )
+    };
+    (@condition [$($target_condition:tt)+] { $($target_then:tt)* } else { $($target_else:tt)* }) => {
+        $crate::Select::select(
+            $crate::branchless!($($target_else)*),
+            $crate::branchless!($($target_then)*),
+            ($($target_condition)+),
+        )
+    };
+    (@condition [$($target_condition:tt)+] { $($target_then:tt)* }) => {
+        ::core::compile_error!("`branchless!` requires every `if` to have an `else` arm")
+    };
+    (@condition [$($target_condition:tt)*] $target_next:tt $($target_rest:tt)*) => {
+        $crate::branchless!(@condition [$($target_condition)* $target_next] $($target_rest)*)
+    };
+
+    // `match` expressions, the scrutinee is gathered token by token up to the trailing block of arms.
+    (match $($target_rest:tt)+) => {
+        $crate::branchless!(@scrutinee [] $($target_rest)+)
+    };
+    (@scrutinee [$($target_scrutinee:tt)+] { $($target_arms:tt)* }) => {{
+        let target_scrutinee = $($target_scrutinee)+;
+
+        $crate::branchless!(@arms target_scrutinee; $($target_arms)*)
+    }};
+    (@scrutinee [$($target_scrutinee:tt)*] $target_next:tt $($target_rest:tt)*) => {
+        $crate::branchless!(@scrutinee [$($target_scrutinee)* $target_next] $($target_rest)*)
+    };
+    (@arms $target_scrutinee:ident; _ => $target_default:expr $(,)?) => {
+        $target_default
+    };
+    (@arms $target_scrutinee:ident; $($target_pattern:literal)|+ => $target_value:expr, $($target_rest:tt)+) => {
+        $crate::Select::select(
+            $crate::branchless!(@arms $target_scrutinee; $($target_rest)+),
+            $target_value,
+            false $(| ($target_scrutinee == $target_pattern))+,
+        )
+    };
+    (@arms $target_scrutinee:ident; $($target_rest:tt)*) => {
+        ::core::compile_error!("`branchless!` requires integer literal patterns and a trailing `_` arm")
+    };
+
+    // Anything else is a plain expression, possibly made of statements.
+    ($($target_expression:tt)*) => {
+        { $($target_expression)* }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn branchless_if_matches_if() {
+        for target_left in -4i32..4 {
+            for target_right in -4i32..4 {
+                let target_expect = if target_left < target_right {
+                    target_left * 2
+                } else if target_left == target_right {
+                    0
+                } else {
+                    target_right - 1
+                };
+
+                let target_value = branchless! {
+                    if target_left < target_right {
+                        target_left * 2
+                    } else if target_left == target_right {
+                        0
+                    } else {
+                        let target_offset = 1;
+
+                        target_right - target_offset
+                    }
+                };
+
+                assert_eq!(target_expect, target_value);
+            }
+        }
+    }
+
+    #[test]
+    fn branchless_nested_if() {
+        for target_left in 0u8..4 {
+            let target_value = branchless! {
+                if target_left > 1 {
+                    if target_left > 2 { 3u8 } else { 2 }
+                } else {
+                    match target_left { 0 => 0, _ => 1 }
+                }
+            };
+
+            assert_eq!(target_left, target_value);
+        }
+    }
+
+    #[test]
+    fn branchless_match_matches_match() {
+        for target_left in -3i64..10 {
+            // The overlapping arm checks that the first match wins.
+            #[allow(unreachable_patterns)]
+            let target_expect: u16 = match target_left.abs() {
+                -1 => 100,
+                0 | 1 => 200,
+                2 => 300,
+                2 | 5 => 400,
+                _ => 500,
+            };
+
+            let target_value: u16 = branchless! {
+                match target_left.abs() {
+                    -1 => 100,
+                    0 | 1 => 200,
+                    2 => 300,
+                    2 | 5 => 400,
+                    _ => 500,
+                }
+            };
+
+            assert_eq!(target_expect, target_value);
+        }
+    }
+}
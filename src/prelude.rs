@@ -1,3 +1,3 @@
 //! A collection of the most common items that exist in the `linearity` crate.
 
-pub use crate::{primitive::Primitive, Filter, Linearity, PointerExt, PointerMutExt};
+pub use crate::{primitive::Primitive, FatPointerExt, Filter, Linearity, PointerExt, PointerMutExt, RefExt};
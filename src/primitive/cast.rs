@@ -70,6 +70,17 @@ macro_rules! impl_tests {
                     assert_eq!(result, target as $target_out);
                 }
 
+                #[test]
+                fn [< cast_ $target_type:snake _as_ $target_out:snake _ cases >]() {
+                    use $crate::primitive::check::{cases, check};
+
+                    check(cases::<$target_type>(), |target| {
+                        let result: $target_out = target.cast();
+
+                        result == target as $target_out
+                    });
+                }
+
                 #[test]
                 fn [< cast_ $target_type:snake _as_ $target_out:snake _ min >]() {
                     let target = <$target_type>::MIN;
@@ -98,8 +109,9 @@ macro_rules! impl_tests {
     };
 }
 
-pub(self) use impl_castable;
-pub(self) use impl_tests;
+use impl_castable;
+#[allow(unused_imports)]
+use impl_tests;
 
 use crate::primitive::primitive_list;
 
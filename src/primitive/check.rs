@@ -0,0 +1,130 @@
+/*
+    linearity: A library for branchless programming
+    Copyright (C) 2024  advantageous-overtake
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! A small property checker over primitive values, for tests.
+//!
+//! Types of at most 16 bits are checked exhaustively, as are pairs of at most 16 bits in total. Wider types are checked
+//! on boundary values, along with [`SAMPLES`] values drawn from a fixed seed so failures are reproducible.
+
+use core::fmt::Debug;
+
+use super::{cast::Cast, Primitive};
+use crate::random::Xorshift;
+
+/// The number of random values drawn for non-exhaustive checks.
+pub(crate) const SAMPLES: usize = 1024;
+
+/// The widest domain, in bits, that is enumerated exhaustively.
+const EXHAUSTIVE_BITS: u32 = 16;
+
+/// The seed every check draws its random values from.
+const SEED: u64 = 0x2545_F491_4F6C_DD1D;
+
+/// Returns `MIN`, `MAX`, zero, their neighbours, and the shift amounts around `BITS`.
+pub(crate) fn boundaries<T>() -> Vec<T>
+where
+    T: Primitive + Cast<u128>,
+    u128: Cast<T>,
+{
+    let target_min: u128 = T::MIN.cast();
+    let target_max: u128 = T::MAX.cast();
+    let target_bits = T::BITS as u128;
+
+    [
+        target_min,
+        target_min.wrapping_add(1),
+        target_max.wrapping_sub(1),
+        target_max,
+        u128::MAX,
+        0,
+        1,
+        target_bits - 1,
+        target_bits,
+        target_bits + 1,
+    ]
+    .into_iter()
+    .map(Cast::cast)
+    .collect()
+}
+
+/// Returns every value of `T` if it is narrow enough, its boundaries along with random values otherwise.
+pub(crate) fn cases<T>() -> Vec<T>
+where
+    T: Primitive + Cast<u128>,
+    u128: Cast<T>,
+{
+    if T::BITS as u32 <= EXHAUSTIVE_BITS {
+        return (0..1u128 << T::BITS).map(Cast::cast).collect();
+    }
+
+    let mut target_random = Xorshift::new(SEED);
+
+    boundaries()
+        .into_iter()
+        .chain((0..SAMPLES).map(|_| target_random.value()))
+        .collect()
+}
+
+/// Returns every pair of `L` and `R` if they are narrow enough, pairs of boundaries along with random pairs otherwise.
+pub(crate) fn pairs<L, R>() -> Vec<(L, R)>
+where
+    L: Primitive + Cast<u128>,
+    R: Primitive + Cast<u128>,
+    u128: Cast<L> + Cast<R>,
+{
+    if L::BITS as u32 + R::BITS as u32 <= EXHAUSTIVE_BITS {
+        return cases::<L>()
+            .into_iter()
+            .flat_map(|target_left| cases::<R>().into_iter().map(move |target_right| (target_left, target_right)))
+            .collect();
+    }
+
+    let mut target_random = Xorshift::new(SEED);
+
+    boundaries::<L>()
+        .into_iter()
+        .flat_map(|target_left| boundaries::<R>().into_iter().map(move |target_right| (target_left, target_right)))
+        .chain((0..SAMPLES).map(|_| (target_random.value(), target_random.value())))
+        .collect()
+}
+
+/// Asserts that `target_property` holds for every case, reporting the first counterexample.
+pub(crate) fn check<T, F>(target_cases: impl IntoIterator<Item = T>, mut target_property: F)
+where
+    T: Copy + Debug,
+    F: FnMut(T) -> bool,
+{
+    for target_case in target_cases {
+        assert!(target_property(target_case), "property does not hold for {target_case:?}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{boundaries, cases, pairs};
+
+    #[test]
+    fn check_domains() {
+        assert_eq!(cases::<u8>().len(), 256);
+        assert_eq!(cases::<i16>().len(), 1 << 16);
+        assert_eq!(pairs::<i8, u8>().len(), 1 << 16);
+        assert!(boundaries::<i32>().contains(&-1));
+        assert!(boundaries::<u64>().contains(&65));
+        assert!(cases::<u128>().contains(&u128::MAX));
+    }
+}
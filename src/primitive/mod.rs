@@ -20,6 +20,8 @@
 
 pub mod op;
 pub mod cast;
+#[cfg(test)]
+pub(crate) mod check;
 
 use cast::Cast;
 use op::Operate;
@@ -212,9 +214,8 @@ macro_rules! marker {
     };
 }
 
-pub(self) use impl_primitive;
-pub(self) use impl_primitive_macros;
-pub(self) use integer_list;
+use impl_primitive;
+use impl_primitive_macros;
 
 integer_list!(impl_primitive);
 
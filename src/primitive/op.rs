@@ -23,7 +23,7 @@ pub mod unary;
 
 use self::{binary::BinOp, unary::UnOp};
 
-pub(self) use paste::item;
+use paste::item;
 
 /// A trait representing a type that can be operated on.
 pub trait Operate: BinOp + UnOp {}
@@ -83,11 +83,9 @@ macro_rules! impl_list_macros {
             };
         }
 
-        pub(self) use binary_list;
-        pub(self) use unary_list;
+        use binary_list;
+        use unary_list;
     };
 }
 
 operate_list!(impl_list_macros);
-
-pub(self) use operate_list;
\ No newline at end of file
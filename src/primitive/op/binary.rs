@@ -135,23 +135,30 @@ macro_rules! impl_tests {
                 // NOTE: We check both for panic-behavior and correctness.
                 #[test]
                 fn [< binop_ $target_type:snake _impl_ $target_integer:snake >]() {
-                    use std::{mem::discriminant, panic::catch_unwind};
+                    use std::{collections::HashMap, panic::catch_unwind};
 
-                    let target_left: $target_integer = random!($target_integer);
-                    let target_right: $target_integer = random!($target_integer);
+                    use $crate::primitive::check::{check, pairs};
 
-                    let target_expect: Result<$target_integer, _> = catch_unwind(|| {
-                        target_left $target_operator target_right
-                    });
+                    /*
+                        Whether an operation panics only depends on its right operand, so panic-behavior is checked once
+                        per right operand, with a left operand of zero, and values are compared directly otherwise.
+                     */
+                    let mut target_panics: HashMap<$target_integer, bool> = HashMap::new();
 
-                    let target_value: Result<$target_integer, _> = catch_unwind(|| {
-                        target_left . [< $target_type:snake >] (target_right)
-                    });
+                    check(pairs::<$target_integer, $target_integer>(), |(target_left, target_right)| {
+                        let target_panic = *target_panics.entry(target_right).or_insert_with(|| {
+                            let target_zero: $target_integer = 0;
 
-                    match (target_expect, target_value) {
-                        (Ok(expect), Ok(value)) => assert_eq!(expect, value),
-                        (target_expect, target_value) => assert_eq!(discriminant(&target_expect), discriminant(&target_value))
-                    }
+                            let target_expect = catch_unwind(|| target_zero $target_operator target_right);
+                            let target_value = catch_unwind(|| target_zero . [< $target_type:snake >] (target_right));
+
+                            assert_eq!(target_expect.is_err(), target_value.is_err(), "panic-behavior differs for {target_right:?}");
+
+                            target_expect.is_err()
+                        });
+
+                        target_panic || (target_left $target_operator target_right) == target_left . [< $target_type:snake >] (target_right)
+                    });
                 }
             )+
         }
@@ -170,8 +177,6 @@ macro_rules! impl_tests {
                     )+
                 };
 
-                use const_random::const_random as random;
-                
 
                 $(
                     $crate::primitive::primitive_list!($crate::primitive::op::binary::impl_tests => ($target_name as $target_operator));
@@ -181,6 +186,7 @@ macro_rules! impl_tests {
     };
 }
 
-pub(self) use impl_tests;
+#[allow(unused_imports)]
+use impl_tests;
 
 binary_list!(impl_tests);
\ No newline at end of file
@@ -128,13 +128,13 @@ macro_rules! impl_tests {
             $(
                 #[test]
                 fn [< unop_ $target_type:snake _impl_ $target_integer:snake >]() {
-                    let target_left: $target_integer = random!($target_integer);
+                    use $crate::primitive::check::{cases, check};
 
-                    let target_expect: $target_integer = $target_operator target_left;
+                    check(cases::<$target_integer>(), |target_left| {
+                        let target_expect: $target_integer = $target_operator target_left;
 
-                    let target_value: $target_integer = target_left . [< $target_type:snake >] ();
-
-                    assert_eq!(target_expect, target_value);
+                        target_left . [< $target_type:snake >] () == target_expect
+                    });
                 }
             )+
         }
@@ -153,8 +153,6 @@ macro_rules! impl_tests {
                     )+
                 };
 
-                use const_random::const_random as random;
-                
 
                 $(
                     $crate::primitive::primitive_list!($crate::primitive::op::unary::impl_tests => ($target_name as $target_operator));
@@ -164,6 +162,7 @@ macro_rules! impl_tests {
     };
 }
 
-pub(self) use impl_tests;
+#[allow(unused_imports)]
+use impl_tests;
 
 unary_list!(impl_tests);
\ No newline at end of file
@@ -0,0 +1,49 @@
+/*
+    linearity: A library for branchless programming
+    Copyright (C) 2024  advantageous-overtake
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! A seeded pseudo-random generator shared by the tests and the timing harness.
+
+use crate::primitive::cast::Cast;
+
+/// A xorshift generator, reproducible from its seed.
+pub(crate) struct Xorshift(u64);
+
+impl Xorshift {
+    /// Creates a generator from `target_seed`, which must be non-zero.
+    pub(crate) const fn new(target_seed: u64) -> Self {
+        Self(target_seed)
+    }
+
+    /// Returns the next word.
+    pub(crate) fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+
+        self.0
+    }
+
+    /// Returns a value of `T`, uniformly drawn.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) fn value<T>(&mut self) -> T
+    where
+        u128: Cast<T>,
+    {
+        ((self.next() as u128) << 64 | self.next() as u128).cast()
+    }
+}
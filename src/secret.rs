@@ -0,0 +1,277 @@
+/*
+    linearity: A library for branchless programming
+    Copyright (C) 2024  advantageous-overtake
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! A wrapper for primitives that must not be branched on.
+//!
+//! See [`Secret`] for more information.
+
+use core::fmt;
+
+use crate::{
+    primitive::{
+        cast::Cast,
+        op::{
+            binary::{BitAnd, BitOr, BitShl, BitShr, BitXor},
+            unary::BitNot,
+        },
+        Primitive,
+    },
+    Filter, Select,
+};
+
+/// A primitive value that can only be operated on without branching.
+///
+/// `Secret` deliberately implements neither [`PartialEq`] nor [`Ord`], and its [`Debug`](fmt::Debug) output is
+/// redacted, so that misuse such as `if target_secret == target_value` fails to compile. Instead, it exposes the
+/// [`op`](crate::primitive::op) traits, comparisons yielding a mask, and selection and filtering through such masks.
+///
+/// A mask is itself a `Secret`, with all bits set if the comparison holds and none otherwise, so the outcome of a
+/// comparison cannot be branched on either.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::{primitive::op::binary::BitXor, Secret};
+///
+/// let target_key = Secret::new(0x5Au8);
+/// let target_guess = Secret::new(0x5Au8);
+///
+/// let target_outcome = Secret::new(0u8).select_masked(Secret::new(1), target_key.eq_mask(target_guess));
+///
+/// assert_eq!(target_outcome.expose(), 1);
+/// assert_eq!(target_key.xor(target_guess).expose(), 0);
+/// ```
+///
+/// ```compile_fail
+/// use linearity::Secret;
+///
+/// let target_key = Secret::new(0x5Au8);
+///
+/// if target_key == Secret::new(0x5A) {}
+/// ```
+///
+/// ```compile_fail
+/// use linearity::{Filter, Secret};
+///
+/// let target_key = Secret::new(0x5Au8);
+///
+/// if target_key.eq_mask(Secret::new(0x5A)) == Filter::Transparent {}
+/// ```
+#[derive(Clone, Copy, Default)]
+#[repr(transparent)]
+pub struct Secret<T>(pub(crate) T);
+
+impl<T> Secret<T>
+where
+    T: Primitive,
+{
+    /// Wraps `target_value`.
+    #[inline]
+    pub const fn new(target_value: T) -> Self {
+        Self(target_value)
+    }
+
+    /// Unwraps the value, declassifying it.
+    #[inline]
+    pub const fn expose(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Secret<T>
+where
+    T: Primitive + Ord,
+    T: BitXor<Operand = T, Output = T> + BitAnd<Operand = T, Output = T>,
+
+    i8: Cast<T>,
+{
+    /// Returns the bits of `target_right` where `target_mask` is set, and those of `self` where it is clear.
+    ///
+    /// With a mask from a comparison, this is `target_right` if the comparison holds, `self` otherwise. Unlike
+    /// [`Select::select`], the condition never leaves the secret domain as a `bool`.
+    #[inline]
+    pub fn select_masked(self, target_right: Self, target_mask: Self) -> Self {
+        let target_operand = self.0.xor(target_right.0);
+
+        Self(self.0.xor(target_operand.and(target_mask.0)))
+    }
+
+    /// Returns the bits of `self` where `target_mask` is set, zero elsewhere.
+    ///
+    /// With a mask from a comparison, this is `self` if the comparison holds, zero otherwise.
+    #[inline]
+    pub fn filter(self, target_mask: Self) -> Self {
+        Self(self.0.and(target_mask.0))
+    }
+
+    /// Returns whether `self` equals `target_right`, as a mask.
+    #[inline]
+    pub fn eq_mask(self, target_right: Self) -> Self {
+        Self(Filter::condition(self.0 == target_right.0))
+    }
+
+    /// Returns whether `self` differs from `target_right`, as a mask.
+    #[inline]
+    pub fn ne_mask(self, target_right: Self) -> Self {
+        Self(Filter::condition(self.0 != target_right.0))
+    }
+
+    /// Returns whether `self` is less than `target_right`, as a mask.
+    #[inline]
+    pub fn lt_mask(self, target_right: Self) -> Self {
+        Self(Filter::condition(self.0 < target_right.0))
+    }
+
+    /// Returns whether `self` is at most `target_right`, as a mask.
+    #[inline]
+    pub fn le_mask(self, target_right: Self) -> Self {
+        Self(Filter::condition(self.0 <= target_right.0))
+    }
+
+    /// Returns whether `self` is greater than `target_right`, as a mask.
+    #[inline]
+    pub fn gt_mask(self, target_right: Self) -> Self {
+        Self(Filter::condition(self.0 > target_right.0))
+    }
+
+    /// Returns whether `self` is at least `target_right`, as a mask.
+    #[inline]
+    pub fn ge_mask(self, target_right: Self) -> Self {
+        Self(Filter::condition(self.0 >= target_right.0))
+    }
+}
+
+impl<T> fmt::Debug for Secret<T> {
+    fn fmt(&self, target_formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        target_formatter.write_str("Secret(..)")
+    }
+}
+
+impl<T> Select for Secret<T>
+where
+    T: Select,
+{
+    #[inline]
+    fn select(target_left: Self, target_right: Self, target_dependence: bool) -> Self {
+        Self(T::select(target_left.0, target_right.0, target_dependence))
+    }
+}
+
+/// Implements binary operation traits for [`Secret`].
+macro_rules! impl_binary {
+    (
+        $(
+            $target_trait:ident as $target_method:ident
+        )+
+    ) => {
+        $(
+            impl<T> $target_trait for Secret<T>
+            where
+                T: $target_trait<Operand = T, Output = T>,
+            {
+                type Operand = Self;
+                type Output = Self;
+
+                #[inline]
+                fn $target_method(self, target_right: Self::Operand) -> Self::Output {
+                    Self(self.0.$target_method(target_right.0))
+                }
+            }
+        )+
+    };
+}
+
+impl_binary! {
+    BitAnd as and
+    BitOr as or
+    BitXor as xor
+    BitShl as shl
+    BitShr as shr
+}
+
+impl<T> BitNot for Secret<T>
+where
+    T: BitNot<Output = T>,
+{
+    type Output = Self;
+
+    #[inline]
+    fn not(self) -> Self::Output {
+        Self(self.0.not())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        primitive::{
+            op::{
+                binary::{BitAnd, BitOr, BitXor},
+                unary::BitNot,
+            },
+            primitive_list,
+        },
+        Select,
+    };
+
+    use super::Secret;
+
+    macro_rules! impl_secret {
+        (
+            $(
+                $target_type:ident
+            )+
+        ) => {
+            paste::item! {
+                $(
+                    #[test]
+                    fn [< secret_ $target_type >]() {
+                        let target_left = Secret::new($target_type::MAX);
+                        let target_right = Secret::new(1 as $target_type);
+
+                        assert_eq!(target_left.eq_mask(target_left).expose(), !0);
+                        assert_eq!(target_left.eq_mask(target_right).expose(), 0);
+
+                        assert_eq!(target_left.select_masked(target_right, target_left.gt_mask(target_right)).expose(), 1);
+                        assert_eq!(target_left.select_masked(target_right, target_left.lt_mask(target_right)).expose(), $target_type::MAX);
+                        assert_eq!(target_left.filter(target_left.eq_mask(target_left)).expose(), $target_type::MAX);
+                        assert_eq!(target_left.filter(target_left.ne_mask(target_left)).expose(), 0);
+                        assert_eq!(target_right.filter(target_right.le_mask(target_right)).expose(), 1);
+                        assert_eq!(target_right.filter(target_right.ge_mask(target_left)).expose(), 0);
+
+                        assert_eq!(Secret::select(target_left, target_right, true).expose(), 1);
+                        assert_eq!(Secret::select(target_left, target_right, false).expose(), $target_type::MAX);
+
+                        assert_eq!(target_left.and(target_right).expose(), $target_type::MAX & 1);
+                        assert_eq!(target_left.or(target_right).expose(), $target_type::MAX | 1);
+                        assert_eq!(target_left.xor(target_right).expose(), $target_type::MAX ^ 1);
+                        assert_eq!(target_right.not().expose(), !1);
+                    }
+                )+
+            }
+        };
+    }
+
+    primitive_list!(impl_secret);
+
+    #[test]
+    fn secret_debug_redacted() {
+        assert_eq!(format!("{:?}", Secret::new(42u32)), "Secret(..)");
+        assert_eq!(format!("{:?}", Secret::new(3u8).eq_mask(Secret::new(3))), "Secret(..)");
+    }
+}
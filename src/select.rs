@@ -0,0 +1,271 @@
+/*
+    linearity: A library for branchless programming
+    Copyright (C) 2024  advantageous-overtake
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Branchless selection for arbitrary types.
+//!
+//! See [`Select`] for more information.
+
+use core::num::{Saturating, Wrapping};
+
+use crate::{primitive::primitive_list, Linearity};
+
+/// Trait for types which can be selected between without branching.
+///
+/// Unlike [`Linearity`], this trait is open: besides primitives, it is implemented for `bool`, arrays, tuples, [`Option`],
+/// [`Wrapping`] and [`Saturating`] of selectable types, and composite types implement it field by field, see the
+/// `Select` derive macro behind the `derive` feature.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::Select;
+///
+/// let mut target_left = 5u16;
+/// let mut target_right = 9u16;
+///
+/// assert_eq!(Select::select(target_left, target_right, true), 9);
+///
+/// Select::conditional_swap(&mut target_left, &mut target_right, true);
+///
+/// assert_eq!((target_left, target_right), (9, 5));
+/// ```
+pub trait Select: Copy {
+    /// Returns `target_right` if `target_dependence` holds, `target_left` otherwise.
+    fn select(target_left: Self, target_right: Self, target_dependence: bool) -> Self;
+
+    /// Assigns `target_other` to `self` if `target_dependence` holds.
+    #[inline]
+    fn conditional_assign(&mut self, target_other: Self, target_dependence: bool) {
+        *self = Self::select(*self, target_other, target_dependence);
+    }
+
+    /// Swaps `target_left` and `target_right` if `target_dependence` holds.
+    #[inline]
+    fn conditional_swap(target_left: &mut Self, target_right: &mut Self, target_dependence: bool) {
+        let target_previous = *target_left;
+
+        *target_left = Self::select(target_previous, *target_right, target_dependence);
+        *target_right = Self::select(*target_right, target_previous, target_dependence);
+    }
+}
+
+/// Implements [`Select`] for primitive types through [`Linearity::select`].
+macro_rules! impl_select {
+    (
+        $(
+            $target_type:ident
+        )+
+    ) => {
+        $(
+            impl Select for $target_type {
+                #[inline]
+                fn select(target_left: Self, target_right: Self, target_dependence: bool) -> Self {
+                    <$target_type as Linearity>::select(target_left, target_right, target_dependence)
+                }
+            }
+        )+
+    };
+}
+
+/// Implements [`Select`] for tuples, element by element.
+macro_rules! impl_select_tuple {
+    (
+        $(
+            ($($target_name:ident $target_index:tt)+)
+        )+
+    ) => {
+        $(
+            impl<$($target_name),+> Select for ($($target_name,)+)
+            where
+                $($target_name: Select,)+
+            {
+                #[inline]
+                fn select(target_left: Self, target_right: Self, target_dependence: bool) -> Self {
+                    (
+                        $(
+                            $target_name::select(target_left.$target_index, target_right.$target_index, target_dependence),
+                        )+
+                    )
+                }
+            }
+        )+
+    };
+}
+
+primitive_list!(impl_select);
+
+impl_select_tuple! {
+    (A 0)
+    (A 0 B 1)
+    (A 0 B 1 C 2)
+    (A 0 B 1 C 2 D 3)
+    (A 0 B 1 C 2 D 3 E 4)
+    (A 0 B 1 C 2 D 3 E 4 F 5)
+    (A 0 B 1 C 2 D 3 E 4 F 5 G 6)
+    (A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7)
+    (A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 I 8)
+    (A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 I 8 J 9)
+    (A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 I 8 J 9 K 10)
+    (A 0 B 1 C 2 D 3 E 4 F 5 G 6 H 7 I 8 J 9 K 10 L 11)
+}
+
+impl Select for bool {
+    #[inline]
+    fn select(target_left: Self, target_right: Self, target_dependence: bool) -> Self {
+        <u8 as Linearity>::select(target_left as u8, target_right as u8, target_dependence) != 0
+    }
+}
+
+impl<T, const N: usize> Select for [T; N]
+where
+    T: Select,
+{
+    #[inline]
+    fn select(target_left: Self, target_right: Self, target_dependence: bool) -> Self {
+        core::array::from_fn(|target_index| {
+            T::select(target_left[target_index], target_right[target_index], target_dependence)
+        })
+    }
+}
+
+/// Both the discriminant and the payload are selected, through [`bool`] and `T` respectively, so the load pattern does
+/// not depend on `target_dependence`.
+///
+/// `T: Default` is required: a `None` carries no payload to select from, so [`Default::default`] stands in for it.
+impl<T> Select for Option<T>
+where
+    T: Select + Default,
+{
+    #[inline]
+    fn select(target_left: Self, target_right: Self, target_dependence: bool) -> Self {
+        let target_some = bool::select(target_left.is_some(), target_right.is_some(), target_dependence);
+        let target_value = T::select(
+            target_left.unwrap_or_default(),
+            target_right.unwrap_or_default(),
+            target_dependence,
+        );
+
+        target_some.then_some(target_value)
+    }
+}
+
+impl<T> Select for Wrapping<T>
+where
+    T: Select,
+{
+    #[inline]
+    fn select(target_left: Self, target_right: Self, target_dependence: bool) -> Self {
+        Wrapping(T::select(target_left.0, target_right.0, target_dependence))
+    }
+}
+
+impl<T> Select for Saturating<T>
+where
+    T: Select,
+{
+    #[inline]
+    fn select(target_left: Self, target_right: Self, target_dependence: bool) -> Self {
+        Saturating(T::select(target_left.0, target_right.0, target_dependence))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Select;
+    use linearity_derive::Select;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Select)]
+    struct Point {
+        x: i32,
+        y: u8,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Select)]
+    struct Pair<T>(T, u64);
+
+    #[derive(Debug, Clone, Copy, PartialEq, Select)]
+    struct Unit;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Select)]
+    #[repr(i16)]
+    enum Direction {
+        Up = -3,
+        Down = 7,
+    }
+
+    #[test]
+    fn select_composites() {
+        use core::num::{Saturating, Wrapping};
+
+        fn pick<T>(target_left: T, target_right: T, target_dependence: bool) -> T {
+            match target_dependence {
+                true => target_right,
+                false => target_left,
+            }
+        }
+
+        for target_dependence in [false, true] {
+
+            assert_eq!(Select::select(true, false, target_dependence), pick(true, false, target_dependence));
+            assert_eq!(Select::select([1u8, 2, 3], [4, 5, 6], target_dependence), pick([1, 2, 3], [4, 5, 6], target_dependence));
+            assert_eq!(Select::select((1i64, false), (-1, true), target_dependence), pick((1, false), (-1, true), target_dependence));
+            assert_eq!(Select::select(Wrapping(3u32), Wrapping(4), target_dependence), pick(Wrapping(3), Wrapping(4), target_dependence));
+            assert_eq!(Select::select(Saturating(3i8), Saturating(4), target_dependence), pick(Saturating(3), Saturating(4), target_dependence));
+
+            for (target_left, target_right) in [(None, None), (Some(7u16), None), (None, Some(9)), (Some(7), Some(9))] {
+                assert_eq!(Select::select(target_left, target_right, target_dependence), pick(target_left, target_right, target_dependence));
+            }
+        }
+    }
+
+    #[test]
+    fn derive_select_struct() {
+        let target_left = Point { x: -1, y: 2 };
+        let target_right = Point { x: 40, y: 200 };
+
+        assert_eq!(Select::select(target_left, target_right, false), target_left);
+        assert_eq!(Select::select(target_left, target_right, true), target_right);
+
+        let target_left = Pair(Point { x: 1, y: 1 }, 5);
+        let target_right = Pair(Point { x: 2, y: 2 }, 6);
+
+        assert_eq!(Select::select(target_left, target_right, true), target_right);
+        assert_eq!(Select::select(Unit, Unit, true), Unit);
+    }
+
+    #[test]
+    fn derive_select_enum() {
+        assert_eq!(Select::select(Direction::Up, Direction::Down, true), Direction::Down);
+        assert_eq!(Select::select(Direction::Up, Direction::Down, false), Direction::Up);
+    }
+
+    #[test]
+    fn derive_select_swap_assign() {
+        let mut target_left = Point { x: 1, y: 2 };
+        let mut target_right = Point { x: 3, y: 4 };
+
+        Select::conditional_swap(&mut target_left, &mut target_right, false);
+        assert_eq!((target_left, target_right), (Point { x: 1, y: 2 }, Point { x: 3, y: 4 }));
+
+        Select::conditional_swap(&mut target_left, &mut target_right, true);
+        assert_eq!((target_left, target_right), (Point { x: 3, y: 4 }, Point { x: 1, y: 2 }));
+
+        target_left.conditional_assign(Point { x: 9, y: 9 }, true);
+        assert_eq!(target_left, Point { x: 9, y: 9 });
+    }
+}
@@ -0,0 +1,349 @@
+/*
+    linearity: A library for branchless programming
+    Copyright (C) 2024  advantageous-overtake
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Branchless stream compaction.
+//!
+//! See [`Cursor`], [`compact_into`] and [`dedup_sorted_branchless`] for more information.
+
+use crate::{Filter, Linearity};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// A write cursor over a mutable slice which advances conditionally.
+///
+/// Every push writes to the current slot, the predicate only decides whether the cursor moves past it. A rejected
+/// value is therefore overwritten by the next push.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::slice::Cursor;
+///
+/// let mut target_buffer = [0; 4];
+/// let mut target_cursor = Cursor::new(&mut target_buffer);
+///
+/// for target_value in [1, 2, 3, 4] {
+///     target_cursor.push_if(target_value, target_value % 2 == 0);
+/// }
+///
+/// assert_eq!(target_cursor.into_written(), &[2, 4]);
+/// ```
+#[derive(Debug)]
+pub struct Cursor<'a, T> {
+    target_slice: &'a mut [T],
+    target_position: usize,
+}
+
+impl<'a, T> Cursor<'a, T> {
+    /// Creates a new `Cursor` positioned at the start of `target_slice`.
+    #[inline]
+    pub fn new(target_slice: &'a mut [T]) -> Self {
+        Self {
+            target_slice,
+            target_position: 0,
+        }
+    }
+
+    /// Returns the number of values written so far.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.target_position
+    }
+
+    /// Returns the number of slots left after the current position.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.target_slice.len() - self.target_position
+    }
+
+    /// Writes `target_value` to the current slot and advances by one if `target_predicate` holds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no slots remaining, regardless of `target_predicate`.
+    #[inline]
+    pub fn push_if(&mut self, target_value: T, target_predicate: bool) {
+        self.target_slice[self.target_position] = target_value;
+
+        self.target_position += 1usize.filter::<usize>(Filter::new(target_predicate));
+    }
+
+    /// Returns the written prefix of the slice.
+    #[inline]
+    pub fn written(&self) -> &[T] {
+        &self.target_slice[..self.target_position]
+    }
+
+    /// Consumes the cursor, returning the written prefix of the slice.
+    #[inline]
+    pub fn into_written(self) -> &'a mut [T] {
+        &mut self.target_slice[..self.target_position]
+    }
+}
+
+/// Writes the elements of `target_source` satisfying `target_predicate` contiguously to the start of `target_destination`.
+///
+/// Every element is written unconditionally, the predicate only decides whether the write position advances.
+///
+/// Returns the number of kept elements.
+///
+/// # Panics
+///
+/// Panics if `target_destination` is shorter than `target_source`.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::slice::compact_into;
+///
+/// let target_source = [5, 1, 7, 3, 9];
+/// let mut target_destination = [0; 5];
+///
+/// let target_count = compact_into(&target_source, &mut target_destination, |target_value| target_value > 4);
+///
+/// assert_eq!(&target_destination[..target_count], &[5, 7, 9]);
+/// ```
+#[inline]
+pub fn compact_into<T, F>(target_source: &[T], target_destination: &mut [T], target_predicate: F) -> usize
+where
+    T: Copy,
+    F: Fn(T) -> bool,
+{
+    assert!(target_destination.len() >= target_source.len());
+
+    let mut target_cursor = Cursor::new(target_destination);
+
+    for &target_value in target_source {
+        target_cursor.push_if(target_value, target_predicate(target_value));
+    }
+
+    target_cursor.position()
+}
+
+/// Moves the elements of `target_slice` satisfying `target_predicate` to its start, preserving their order.
+///
+/// Returns the number of kept elements, the contents of the remaining tail are unspecified.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::slice::compact_in_place;
+///
+/// let mut target_slice = [5, 1, 7, 3, 9];
+///
+/// let target_count = compact_in_place(&mut target_slice, |target_value| target_value > 4);
+///
+/// assert_eq!(&target_slice[..target_count], &[5, 7, 9]);
+/// ```
+#[inline]
+pub fn compact_in_place<T, F>(target_slice: &mut [T], target_predicate: F) -> usize
+where
+    T: Copy,
+    F: Fn(T) -> bool,
+{
+    let mut target_position = 0;
+
+    for target_index in 0..target_slice.len() {
+        let target_value = target_slice[target_index];
+
+        // `target_position` never exceeds `target_index`, so nothing unread is overwritten.
+        target_slice[target_position] = target_value;
+        target_position += 1usize.filter::<usize>(Filter::new(target_predicate(target_value)));
+    }
+
+    target_position
+}
+
+/// Removes consecutive repeated elements of `target_slice`, moving the first of every run to its start.
+///
+/// Every element is written unconditionally, the write position advancing only when the element differs from the last
+/// one kept. On a sorted slice this leaves every distinct element exactly once.
+///
+/// Returns the number of kept elements, the contents of the remaining tail are unspecified.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::slice::dedup_sorted_branchless;
+///
+/// let mut target_slice = [1, 1, 2, 3, 3, 3, 8];
+///
+/// let target_count = dedup_sorted_branchless(&mut target_slice);
+///
+/// assert_eq!(&target_slice[..target_count], &[1, 2, 3, 8]);
+/// ```
+#[inline]
+pub fn dedup_sorted_branchless<T>(target_slice: &mut [T]) -> usize
+where
+    T: Copy + PartialEq,
+{
+    if target_slice.is_empty() {
+        return 0;
+    }
+
+    let mut target_position = 1;
+
+    for target_index in 1..target_slice.len() {
+        let target_value = target_slice[target_index];
+        let target_distinct = target_value != target_slice[target_position - 1];
+
+        target_slice[target_position] = target_value;
+        target_position += 1usize.filter::<usize>(Filter::new(target_distinct));
+    }
+
+    target_position
+}
+
+/// Returns the number of distinct elements of the sorted `target_slice`.
+///
+/// This is the count [`dedup_sorted_branchless`] would keep, without modifying the slice.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::slice::count_distinct_sorted;
+///
+/// assert_eq!(count_distinct_sorted(&[1, 1, 2, 3, 3, 3, 8]), 4);
+/// assert_eq!(count_distinct_sorted::<u8>(&[]), 0);
+/// ```
+#[inline]
+pub fn count_distinct_sorted<T>(target_slice: &[T]) -> usize
+where
+    T: PartialEq,
+{
+    let target_boundaries: usize = target_slice
+        .windows(2)
+        .map(|target_pair| 1usize.filter::<usize>(Filter::new(target_pair[0] != target_pair[1])))
+        .sum();
+
+    // The first element always starts a run.
+    target_boundaries + target_slice.len().min(1)
+}
+
+/// Retains only the elements satisfying `target_predicate`, as per [`Vec::retain`] but without data-dependent branches.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::slice::retain_branchless;
+///
+/// let mut target_vec = vec![5, 1, 7, 3, 9];
+///
+/// retain_branchless(&mut target_vec, |target_value| target_value > 4);
+///
+/// assert_eq!(target_vec, [5, 7, 9]);
+/// ```
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn retain_branchless<T, F>(target_vec: &mut Vec<T>, target_predicate: F)
+where
+    T: Copy,
+    F: Fn(T) -> bool,
+{
+    let target_count = compact_in_place(target_vec, target_predicate);
+
+    target_vec.truncate(target_count);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::random::Xorshift;
+
+    use super::{compact_in_place, compact_into, count_distinct_sorted, dedup_sorted_branchless, Cursor};
+
+    #[test]
+    fn cursor_push_if() {
+        let target_source: [u32; 8] = [3, 8, 1, 9, 4, 4, 7, 2];
+        let mut target_buffer = [0; 8];
+
+        let mut target_cursor = Cursor::new(&mut target_buffer);
+
+        for target_value in target_source {
+            target_cursor.push_if(target_value, target_value > 3);
+        }
+
+        assert_eq!(target_cursor.position(), 5);
+        assert_eq!(target_cursor.remaining(), 3);
+        assert_eq!(target_cursor.written(), &[8, 9, 4, 4, 7]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn cursor_push_if_full() {
+        let mut target_buffer = [0u8; 1];
+        let mut target_cursor = Cursor::new(&mut target_buffer);
+
+        target_cursor.push_if(1, true);
+        target_cursor.push_if(2, false);
+    }
+
+    #[test]
+    fn compact_into_matches_filter() {
+        let target_source: [i16; 10] = [-4, 12, 0, 7, -1, 33, 2, 2, -90, 5];
+        let mut target_destination = [0; 10];
+
+        let target_count = compact_into(&target_source, &mut target_destination, |target_value| target_value >= 2);
+
+        let target_expect: Vec<i16> = target_source.iter().copied().filter(|&target_value| target_value >= 2).collect();
+
+        assert_eq!(&target_destination[..target_count], &target_expect[..]);
+    }
+
+    #[test]
+    fn compact_in_place_matches_filter() {
+        let mut target_slice: [u64; 6] = [1, 2, 3, 4, 5, 6];
+
+        let target_count = compact_in_place(&mut target_slice, |target_value| target_value % 3 != 0);
+
+        assert_eq!(&target_slice[..target_count], &[1, 2, 4, 5]);
+    }
+
+    #[test]
+    fn dedup_sorted_branchless_matches_dedup() {
+        let mut target_random = Xorshift::new(0x2545_F491);
+
+        for target_length in 0..64 {
+            let mut target_value: Vec<u32> = (0..target_length).map(|_| target_random.next() as u32 % 8).collect();
+
+            target_value.sort_unstable();
+
+            let mut target_expect = target_value.clone();
+
+            target_expect.dedup();
+
+            assert_eq!(count_distinct_sorted(&target_value), target_expect.len());
+
+            let target_count = dedup_sorted_branchless(&mut target_value);
+
+            assert_eq!(&target_value[..target_count], &target_expect[..]);
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn retain_branchless_matches_retain() {
+        let mut target_expect: Vec<u8> = (0..=255).collect();
+        let mut target_value = target_expect.clone();
+
+        target_expect.retain(|&target_element| target_element.count_ones() > 4);
+        super::retain_branchless(&mut target_value, |target_element| target_element.count_ones() > 4);
+
+        assert_eq!(target_expect, target_value);
+    }
+}
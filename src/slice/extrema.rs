@@ -0,0 +1,208 @@
+/*
+    linearity: A library for branchless programming
+    Copyright (C) 2024  advantageous-overtake
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Branchless extrema and top-k selection.
+//!
+//! The running best value and its index are tracked with [`Select::select`], so the loops carry no branch on the
+//! data. Ties are resolved in favour of the first occurrence.
+
+use crate::Select;
+
+/// Returns the index of the first element for which `target_better` holds against every other one.
+#[inline(always)]
+fn arg_by<T, F>(target_slice: &[T], target_better: F) -> Option<usize>
+where
+    T: Select,
+
+    F: Fn(T, T) -> bool,
+{
+    let (&target_first, target_rest) = target_slice.split_first()?;
+
+    let mut target_best = target_first;
+    let mut target_best_index: usize = 0;
+
+    for (target_index, &target_value) in target_rest.iter().enumerate() {
+        let target_dependence = target_better(target_value, target_best);
+
+        target_best = T::select(target_best, target_value, target_dependence);
+        target_best_index = usize::select(target_best_index, target_index + 1, target_dependence);
+    }
+
+    Some(target_best_index)
+}
+
+/// Returns the index of the first minimum of `target_slice`, or `None` if it is empty.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::slice::argmin;
+///
+/// assert_eq!(argmin(&[4, -2, 7, -2]), Some(1));
+/// assert_eq!(argmin::<u8>(&[]), None);
+/// ```
+#[inline]
+pub fn argmin<T>(target_slice: &[T]) -> Option<usize>
+where
+    T: Select + Ord,
+{
+    arg_by(target_slice, |target_value, target_best| target_value < target_best)
+}
+
+/// Returns the index of the first maximum of `target_slice`, or `None` if it is empty.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::slice::argmax;
+///
+/// assert_eq!(argmax(&[4, 9, 7, 9]), Some(1));
+/// ```
+#[inline]
+pub fn argmax<T>(target_slice: &[T]) -> Option<usize>
+where
+    T: Select + Ord,
+{
+    arg_by(target_slice, |target_value, target_best| target_value > target_best)
+}
+
+/// Returns the minimum and the maximum of `target_slice`, or `None` if it is empty.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::slice::min_max;
+///
+/// assert_eq!(min_max(&[4u16, 9, 1, 7]), Some((1, 9)));
+/// ```
+#[inline]
+pub fn min_max<T>(target_slice: &[T]) -> Option<(T, T)>
+where
+    T: Select + Ord,
+{
+    let (&target_first, target_rest) = target_slice.split_first()?;
+
+    let mut target_minimum = target_first;
+    let mut target_maximum = target_first;
+
+    for &target_value in target_rest {
+        target_minimum = T::select(target_minimum, target_value, target_value < target_minimum);
+        target_maximum = T::select(target_maximum, target_value, target_value > target_maximum);
+    }
+
+    Some((target_minimum, target_maximum))
+}
+
+/// Inserts `target_value` into the descending `target_buffer`, dropping its last element.
+#[inline(always)]
+fn insert_descending<T>(target_buffer: &mut [T], target_value: T)
+where
+    T: Select + Ord,
+{
+    // Walk backwards, so every slot still sees the previous value of its predecessor.
+    for target_index in (1..target_buffer.len()).rev() {
+        let target_current = target_buffer[target_index];
+        let target_previous = target_buffer[target_index - 1];
+
+        let target_shifted = T::select(target_current, target_value, target_value > target_current);
+
+        target_buffer[target_index] = T::select(target_shifted, target_previous, target_value > target_previous);
+    }
+
+    if let Some(target_head) = target_buffer.first_mut() {
+        target_head.conditional_assign(target_value, target_value > *target_head);
+    }
+}
+
+/// Returns the `K` greatest elements of `target_slice` in descending order, or `None` if it has fewer than `K`.
+///
+/// Every element is inserted conditionally into a buffer kept in descending order: each slot independently selects
+/// between keeping its value, taking the new one or taking its predecessor. The first `K` elements are inserted into a
+/// growing prefix of the buffer, so `K` is not limited.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::slice::top_k;
+///
+/// assert_eq!(top_k::<_, 3>(&[5, 1, 9, 3, 7, 9]), Some([9, 9, 7]));
+/// assert_eq!(top_k::<i32, 3>(&[5, 1]), None);
+/// ```
+#[inline]
+pub fn top_k<T, const K: usize>(target_slice: &[T]) -> Option<[T; K]>
+where
+    T: Select + Ord,
+{
+    let (target_seed, target_rest) = target_slice.split_first_chunk::<K>()?;
+
+    let mut target_buffer = *target_seed;
+
+    for target_length in 1..K {
+        let target_value = target_buffer[target_length];
+
+        insert_descending(&mut target_buffer[..=target_length], target_value);
+    }
+
+    for &target_value in target_rest {
+        insert_descending(&mut target_buffer, target_value);
+    }
+
+    Some(target_buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::random::Xorshift;
+
+    use super::{argmax, argmin, min_max, top_k};
+
+    #[test]
+    fn extrema_match_iterators() {
+        let mut target_random = Xorshift::new(0x7777_AAAA_5555_3333);
+
+        for target_length in 0..100 {
+            let target_value: Vec<i16> = (0..target_length).map(|_| target_random.next() as i16 % 64).collect();
+
+            let target_minimum = target_value.iter().min();
+            let target_maximum = target_value.iter().max();
+
+            assert_eq!(argmin(&target_value), target_minimum.and_then(|target_minimum| target_value.iter().position(|target_element| target_element == target_minimum)));
+            assert_eq!(argmax(&target_value), target_maximum.and_then(|target_maximum| target_value.iter().position(|target_element| target_element == target_maximum)));
+            assert_eq!(min_max(&target_value), target_minimum.copied().zip(target_maximum.copied()));
+        }
+    }
+
+    #[test]
+    fn top_k_matches_sort() {
+        let mut target_random = Xorshift::new(0x1357_9BDF_2468_ACE0);
+
+        for target_length in 0..100 {
+            let target_value: Vec<u8> = (0..target_length).map(|_| target_random.next() as u8 % 32).collect();
+
+            let mut target_expect = target_value.clone();
+
+            target_expect.sort_unstable_by(|target_left, target_right| target_right.cmp(target_left));
+
+            assert_eq!(top_k::<_, 0>(&target_value).map(Vec::from), Some(Vec::new()));
+            assert_eq!(top_k::<_, 1>(&target_value).map(Vec::from), target_expect.get(..1).map(Vec::from));
+            assert_eq!(top_k::<_, 5>(&target_value).map(Vec::from), target_expect.get(..5).map(Vec::from));
+            assert_eq!(top_k::<_, 16>(&target_value).map(Vec::from), target_expect.get(..16).map(Vec::from));
+            assert_eq!(top_k::<_, 40>(&target_value).map(Vec::from), target_expect.get(..40).map(Vec::from));
+        }
+    }
+}
@@ -0,0 +1,202 @@
+/*
+    linearity: A library for branchless programming
+    Copyright (C) 2024  advantageous-overtake
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Branchless searches over an Eytzinger (breadth-first) layout.
+//!
+//! See [`Eytzinger`] for more information.
+
+use crate::{Filter, Linearity};
+
+/// A sorted sequence laid out in breadth-first order over caller-provided storage.
+///
+/// Node `k` has children `2 * k` and `2 * k + 1`, the root being node `1`. Slot `0` of the value storage is left
+/// untouched, while slot `0` of the index storage holds the length, which is what a search past the end resolves to.
+///
+/// Searches descend the tree with `2 * k + (target_key > value)`, computed with [`Filter`] rather than a branch.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::slice::Eytzinger;
+///
+/// let target_sorted = [2, 3, 5, 7, 11, 13];
+///
+/// let mut target_values = [0; 7];
+/// let mut target_indices = [0; 7];
+///
+/// let target_layout = Eytzinger::new(&target_sorted, &mut target_values, &mut target_indices);
+///
+/// assert_eq!(target_layout.lower_bound(&6), 3);
+/// assert_eq!(target_layout.find(&11), Some(4));
+/// assert_eq!(target_layout.find(&4), None);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Eytzinger<'a, T> {
+    target_values: &'a [T],
+    target_indices: &'a [usize],
+}
+
+impl<'a, T> Eytzinger<'a, T>
+where
+    T: Copy + Ord,
+{
+    /// Lays out `target_sorted` in `target_values`, recording the original index of every node in `target_indices`.
+    ///
+    /// `target_sorted` must be sorted in ascending order, otherwise search results are unspecified.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either storage is not exactly one element longer than `target_sorted`.
+    pub fn new(target_sorted: &[T], target_values: &'a mut [T], target_indices: &'a mut [usize]) -> Self {
+        let target_length = target_sorted.len();
+
+        assert_eq!(target_values.len(), target_length + 1);
+        assert_eq!(target_indices.len(), target_length + 1);
+
+        target_indices[0] = target_length;
+
+        Self::fill(target_sorted, target_values, target_indices, 0, 1);
+
+        Self {
+            target_values,
+            target_indices,
+        }
+    }
+
+    /// Fills the subtree rooted at `target_node` in order, starting at `target_index`.
+    ///
+    /// Returns the index following the last one consumed.
+    fn fill(
+        target_sorted: &[T],
+        target_values: &mut [T],
+        target_indices: &mut [usize],
+        mut target_index: usize,
+        target_node: usize,
+    ) -> usize {
+        if target_node <= target_sorted.len() {
+            target_index = Self::fill(target_sorted, target_values, target_indices, target_index, 2 * target_node);
+
+            target_values[target_node] = target_sorted[target_index];
+            target_indices[target_node] = target_index;
+
+            target_index = Self::fill(target_sorted, target_values, target_indices, target_index + 1, 2 * target_node + 1);
+        }
+
+        target_index
+    }
+
+    /// Returns the number of elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.target_values.len() - 1
+    }
+
+    /// Returns `true` if there are no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the elements in breadth-first order.
+    #[inline]
+    pub fn as_slice(&self) -> &'a [T] {
+        &self.target_values[1..]
+    }
+
+    /// Descends while `target_predicate` holds, returning the node of the first element for which it does not.
+    ///
+    /// Returns node `0` if it holds for every element.
+    #[inline(always)]
+    fn descend<F>(&self, target_predicate: F) -> usize
+    where
+        F: Fn(&T) -> bool,
+    {
+        let target_length = self.len();
+        let mut target_node = 1;
+
+        while target_node <= target_length {
+            // SAFETY: `target_node` is within `1..=target_length`, the storage holds `target_length + 1` elements.
+            let target_value = unsafe { self.target_values.get_unchecked(target_node) };
+
+            target_node = 2 * target_node + 1usize.filter::<usize>(Filter::new(target_predicate(target_value)));
+        }
+
+        // Undo the trailing right turns, plus the last left turn.
+        target_node >> (target_node.trailing_ones() + 1)
+    }
+
+    /// Returns the original index of the first element not less than `target_key`.
+    ///
+    /// If every element is less than `target_key`, the length is returned.
+    #[inline]
+    pub fn lower_bound(&self, target_key: &T) -> usize {
+        let target_node = self.descend(|target_value| target_value < target_key);
+
+        self.target_indices[target_node]
+    }
+
+    /// Returns the original index of the first element greater than `target_key`.
+    ///
+    /// If no element is greater than `target_key`, the length is returned.
+    #[inline]
+    pub fn upper_bound(&self, target_key: &T) -> usize {
+        let target_node = self.descend(|target_value| target_value <= target_key);
+
+        self.target_indices[target_node]
+    }
+
+    /// Returns the original index of an element equal to `target_key`, if any.
+    #[inline]
+    pub fn find(&self, target_key: &T) -> Option<usize> {
+        let target_node = self.descend(|target_value| target_value < target_key);
+
+        // Node `0` does not hold an element, so it never matches.
+        (target_node != 0 && self.target_values[target_node] == *target_key).then(|| self.target_indices[target_node])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Eytzinger;
+
+    #[test]
+    fn eytzinger_matches_partition_point() {
+        for target_length in 0..40usize {
+            let target_sorted: Vec<i64> = (0..target_length as i64).map(|target_index| target_index / 2 * 3).collect();
+
+            let mut target_values = vec![0; target_length + 1];
+            let mut target_indices = vec![0; target_length + 1];
+
+            let target_layout = Eytzinger::new(&target_sorted, &mut target_values, &mut target_indices);
+
+            assert_eq!(target_layout.len(), target_length);
+
+            for target_key in -2..(target_length as i64 * 2) {
+                let target_lower = target_sorted.partition_point(|&target_value| target_value < target_key);
+                let target_upper = target_sorted.partition_point(|&target_value| target_value <= target_key);
+
+                assert_eq!(target_layout.lower_bound(&target_key), target_lower);
+                assert_eq!(target_layout.upper_bound(&target_key), target_upper);
+                assert_eq!(
+                    target_layout.find(&target_key),
+                    target_sorted.binary_search(&target_key).ok().map(|_| target_lower)
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,41 @@
+/*
+    linearity: A library for branchless programming
+    Copyright (C) 2024  advantageous-overtake
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Branchless algorithms over slices.
+
+pub mod compact;
+pub mod eytzinger;
+pub mod extrema;
+pub mod mux;
+pub mod search;
+pub mod set;
+
+pub use compact::{
+    compact_in_place, compact_into, count_distinct_sorted, dedup_sorted_branchless, Cursor,
+};
+pub use extrema::{argmax, argmin, min_max, top_k};
+pub use eytzinger::Eytzinger;
+pub use mux::select_index;
+pub use search::{
+    branchless_lower_bound, branchless_lower_bound_prefetch, branchless_upper_bound,
+    branchless_upper_bound_prefetch,
+};
+pub use set::{difference_sorted, intersect_sorted, union_sorted};
+
+#[cfg(feature = "alloc")]
+pub use compact::retain_branchless;
@@ -0,0 +1,83 @@
+/*
+    linearity: A library for branchless programming
+    Copyright (C) 2024  advantageous-overtake
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Branchless multiplexing over slices.
+//!
+//! See [`select_index`] for more information.
+
+use crate::{
+    primitive::{
+        cast::Cast,
+        op::binary::{BitAnd, BitOr},
+    },
+    Filter, Linearity,
+};
+
+/// Returns the element of `target_values` at `target_index`, reading every element.
+///
+/// This is the slice counterpart of [`Linearity::select_index`]: the access pattern does not depend on `target_index`.
+/// If `target_index` is out of bounds, zero is returned.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::slice::select_index;
+///
+/// let target_values = [7u64, 11, 13];
+///
+/// assert_eq!(select_index(&target_values, 1), 11);
+/// assert_eq!(select_index(&target_values, 3), 0);
+/// ```
+#[inline]
+pub fn select_index<T>(target_values: &[T], target_index: usize) -> T
+where
+    T: Linearity,
+    T: BitOr<Operand = T, Output = T> + BitAnd<Operand = T, Output = T>,
+
+    i8: Cast<T>,
+{
+    let mut target_outcome: T = Filter::condition(false);
+
+    for (target_position, &target_value) in target_values.iter().enumerate() {
+        // NOTE: The difference is zero only at the matching position, where the widened subtraction borrows into the
+        //       upper half. A comparison would let the optimizer see that only one position can match, and lower the
+        //       loop to jumps.
+        let target_difference = (target_position ^ target_index) as u128;
+        let select_mask: T = ((target_difference.wrapping_sub(1) >> u64::BITS) as i8).cast();
+
+        target_outcome = target_outcome.or(target_value.and(select_mask));
+    }
+
+    target_outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::select_index;
+
+    #[test]
+    fn select_index_matches_get() {
+        let target_values: Vec<i32> = (0..50).map(|target_index| target_index * 7 - 100).collect();
+
+        for target_index in 0..60 {
+            assert_eq!(select_index(&target_values, target_index), target_values.get(target_index).copied().unwrap_or(0));
+        }
+
+        assert_eq!(select_index(&target_values, usize::MAX), 0);
+    }
+}
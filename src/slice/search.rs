@@ -0,0 +1,175 @@
+/*
+    linearity: A library for branchless programming
+    Copyright (C) 2024  advantageous-overtake
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Branchless searches over sorted slices.
+//!
+//! See [`branchless_lower_bound`] for more information.
+
+use crate::{Filter, Linearity};
+
+/// Hints the processor to fetch the cache line containing `target_pointer`.
+///
+/// The pointer is never dereferenced, so it may point out of bounds.
+#[inline(always)]
+fn prefetch<T>(target_pointer: *const T) {
+    #[cfg(target_arch = "x86_64")]
+    {
+        use core::arch::x86_64::{_mm_prefetch, _MM_HINT_T0};
+
+        // SAFETY: Prefetching is a hint and never faults, regardless of the address.
+        unsafe { _mm_prefetch::<_MM_HINT_T0>(target_pointer as *const i8) }
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    let _ = target_pointer;
+}
+
+/// Returns the index of the first element for which `target_predicate` does not hold.
+///
+/// The only per-step decision is a selection of the base index, the loop trip count depends on the length alone.
+#[inline(always)]
+fn partition_point<T, F, const PREFETCH: bool>(target_slice: &[T], target_predicate: F) -> usize
+where
+    F: Fn(&T) -> bool,
+{
+    let mut target_base = 0;
+    let mut target_size = target_slice.len();
+
+    if target_size == 0 {
+        return 0;
+    }
+
+    while target_size > 1 {
+        let target_half = target_size / 2;
+        let target_middle = target_base + target_half;
+
+        if PREFETCH {
+            // Both candidates for the next probe, whichever half is selected.
+            let target_pointer = target_slice.as_ptr();
+
+            prefetch(target_pointer.wrapping_add(target_base + (target_size - target_half) / 2));
+            prefetch(target_pointer.wrapping_add(target_middle + (target_size - target_half) / 2));
+        }
+
+        // SAFETY: `target_middle` is below `target_base + target_size`, which never exceeds the length.
+        let target_value = unsafe { target_slice.get_unchecked(target_middle) };
+
+        target_base = target_base.select(target_middle, target_predicate(target_value));
+        target_size -= target_half;
+    }
+
+    // SAFETY: `target_base` is below the length, as `target_size` is at least one.
+    let target_value = unsafe { target_slice.get_unchecked(target_base) };
+
+    target_base + 1usize.filter::<usize>(Filter::new(target_predicate(target_value)))
+}
+
+/// Returns the index of the first element of `target_slice` not less than `target_key`.
+///
+/// `target_slice` must be sorted in ascending order, otherwise the result is unspecified. If every element is less than
+/// `target_key`, the length of `target_slice` is returned.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::slice::branchless_lower_bound;
+///
+/// let target_slice = [1, 3, 3, 3, 7];
+///
+/// assert_eq!(branchless_lower_bound(&target_slice, &3), 1);
+/// assert_eq!(branchless_lower_bound(&target_slice, &4), 4);
+/// assert_eq!(branchless_lower_bound(&target_slice, &8), 5);
+/// ```
+#[inline]
+pub fn branchless_lower_bound<T>(target_slice: &[T], target_key: &T) -> usize
+where
+    T: Ord,
+{
+    partition_point::<_, _, false>(target_slice, |target_value| target_value < target_key)
+}
+
+/// Returns the index of the first element of `target_slice` greater than `target_key`.
+///
+/// `target_slice` must be sorted in ascending order, otherwise the result is unspecified. If no element is greater than
+/// `target_key`, the length of `target_slice` is returned.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::slice::branchless_upper_bound;
+///
+/// let target_slice = [1, 3, 3, 3, 7];
+///
+/// assert_eq!(branchless_upper_bound(&target_slice, &3), 4);
+/// assert_eq!(branchless_upper_bound(&target_slice, &0), 0);
+/// ```
+#[inline]
+pub fn branchless_upper_bound<T>(target_slice: &[T], target_key: &T) -> usize
+where
+    T: Ord,
+{
+    partition_point::<_, _, false>(target_slice, |target_value| target_value <= target_key)
+}
+
+/// Same as [`branchless_lower_bound`], but prefetches both candidates of the next probe.
+///
+/// This pays off on slices which do not fit in cache.
+#[inline]
+pub fn branchless_lower_bound_prefetch<T>(target_slice: &[T], target_key: &T) -> usize
+where
+    T: Ord,
+{
+    partition_point::<_, _, true>(target_slice, |target_value| target_value < target_key)
+}
+
+/// Same as [`branchless_upper_bound`], but prefetches both candidates of the next probe.
+///
+/// This pays off on slices which do not fit in cache.
+#[inline]
+pub fn branchless_upper_bound_prefetch<T>(target_slice: &[T], target_key: &T) -> usize
+where
+    T: Ord,
+{
+    partition_point::<_, _, true>(target_slice, |target_value| target_value <= target_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        branchless_lower_bound, branchless_lower_bound_prefetch, branchless_upper_bound,
+        branchless_upper_bound_prefetch,
+    };
+
+    #[test]
+    fn bounds_match_partition_point() {
+        for target_length in 0..40usize {
+            // Every other value is duplicated, so both bounds differ on present keys.
+            let target_slice: Vec<i32> = (0..target_length as i32).map(|target_index| target_index / 2 * 3).collect();
+
+            for target_key in -2..(target_length as i32 * 2) {
+                let target_lower = target_slice.partition_point(|&target_value| target_value < target_key);
+                let target_upper = target_slice.partition_point(|&target_value| target_value <= target_key);
+
+                assert_eq!(branchless_lower_bound(&target_slice, &target_key), target_lower);
+                assert_eq!(branchless_upper_bound(&target_slice, &target_key), target_upper);
+                assert_eq!(branchless_lower_bound_prefetch(&target_slice, &target_key), target_lower);
+                assert_eq!(branchless_upper_bound_prefetch(&target_slice, &target_key), target_upper);
+            }
+        }
+    }
+}
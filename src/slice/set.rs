@@ -0,0 +1,218 @@
+/*
+    linearity: A library for branchless programming
+    Copyright (C) 2024  advantageous-overtake
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Branchless operations over sorted sets.
+//!
+//! Both inputs must be sorted in ascending order, otherwise results are unspecified. Duplicates are treated as distinct
+//! elements, as for multisets. Every step writes its output slot unconditionally and advances the cursors by masked
+//! increments.
+
+use crate::{Filter, Linearity, RefExt};
+
+/// Writes the elements present in both `target_left` and `target_right` to `target_output`, returning their count.
+///
+/// # Panics
+///
+/// Panics if `target_output` is shorter than the shorter input.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::slice::intersect_sorted;
+///
+/// let mut target_output = [0; 4];
+///
+/// let target_count = intersect_sorted(&[1, 3, 5, 7], &[3, 4, 5, 6], &mut target_output);
+///
+/// assert_eq!(&target_output[..target_count], &[3, 5]);
+/// ```
+#[inline]
+pub fn intersect_sorted<T>(target_left: &[T], target_right: &[T], target_output: &mut [T]) -> usize
+where
+    T: Copy + Ord,
+{
+    assert!(target_output.len() >= target_left.len().min(target_right.len()));
+
+    let mut target_left_index = 0;
+    let mut target_right_index = 0;
+    let mut target_output_index = 0;
+
+    while target_left_index < target_left.len() && target_right_index < target_right.len() {
+        let target_left_head = target_left[target_left_index];
+        let target_right_head = target_right[target_right_index];
+
+        let target_less = target_left_head < target_right_head;
+        let target_greater = target_right_head < target_left_head;
+
+        target_output[target_output_index] = target_left_head;
+
+        target_output_index += 1usize.filter::<usize>(Filter::new(!target_less & !target_greater));
+        target_left_index += 1usize.filter::<usize>(Filter::new(!target_greater));
+        target_right_index += 1usize.filter::<usize>(Filter::new(!target_less));
+    }
+
+    target_output_index
+}
+
+/// Writes the elements present in either `target_left` or `target_right` to `target_output`, returning their count.
+///
+/// Elements present in both are written once.
+///
+/// # Panics
+///
+/// Panics if `target_output` is shorter than both inputs combined.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::slice::union_sorted;
+///
+/// let mut target_output = [0; 8];
+///
+/// let target_count = union_sorted(&[1, 3, 5, 7], &[3, 4, 5, 6], &mut target_output);
+///
+/// assert_eq!(&target_output[..target_count], &[1, 3, 4, 5, 6, 7]);
+/// ```
+#[inline]
+pub fn union_sorted<T>(target_left: &[T], target_right: &[T], target_output: &mut [T]) -> usize
+where
+    T: Copy + Ord,
+{
+    assert!(target_output.len() >= target_left.len() + target_right.len());
+
+    let mut target_left_index = 0;
+    let mut target_right_index = 0;
+    let mut target_output_index = 0;
+
+    while target_left_index < target_left.len() && target_right_index < target_right.len() {
+        let target_left_head = &target_left[target_left_index];
+        let target_right_head = &target_right[target_right_index];
+
+        let target_less = target_left_head < target_right_head;
+        let target_greater = target_right_head < target_left_head;
+
+        target_output[target_output_index] = *target_left_head.select(target_right_head, target_greater);
+
+        target_output_index += 1;
+        target_left_index += 1usize.filter::<usize>(Filter::new(!target_greater));
+        target_right_index += 1usize.filter::<usize>(Filter::new(!target_less));
+    }
+
+    // At most one of both tails is non-empty.
+    for target_tail in [&target_left[target_left_index..], &target_right[target_right_index..]] {
+        target_output[target_output_index..target_output_index + target_tail.len()].copy_from_slice(target_tail);
+        target_output_index += target_tail.len();
+    }
+
+    target_output_index
+}
+
+/// Writes the elements of `target_left` absent from `target_right` to `target_output`, returning their count.
+///
+/// # Panics
+///
+/// Panics if `target_output` is shorter than `target_left`.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::slice::difference_sorted;
+///
+/// let mut target_output = [0; 4];
+///
+/// let target_count = difference_sorted(&[1, 3, 5, 7], &[3, 4, 5, 6], &mut target_output);
+///
+/// assert_eq!(&target_output[..target_count], &[1, 7]);
+/// ```
+#[inline]
+pub fn difference_sorted<T>(target_left: &[T], target_right: &[T], target_output: &mut [T]) -> usize
+where
+    T: Copy + Ord,
+{
+    assert!(target_output.len() >= target_left.len());
+
+    let mut target_left_index = 0;
+    let mut target_right_index = 0;
+    let mut target_output_index = 0;
+
+    while target_left_index < target_left.len() && target_right_index < target_right.len() {
+        let target_left_head = target_left[target_left_index];
+        let target_right_head = target_right[target_right_index];
+
+        let target_less = target_left_head < target_right_head;
+        let target_greater = target_right_head < target_left_head;
+
+        target_output[target_output_index] = target_left_head;
+
+        target_output_index += 1usize.filter::<usize>(Filter::new(target_less));
+        target_left_index += 1usize.filter::<usize>(Filter::new(!target_greater));
+        target_right_index += 1usize.filter::<usize>(Filter::new(!target_less));
+    }
+
+    let target_tail = &target_left[target_left_index..];
+
+    target_output[target_output_index..target_output_index + target_tail.len()].copy_from_slice(target_tail);
+
+    target_output_index + target_tail.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::random::Xorshift;
+
+    use super::{difference_sorted, intersect_sorted, union_sorted};
+
+    fn sorted_set(target_random: &mut Xorshift, target_length: usize) -> Vec<u32> {
+        let mut target_value: Vec<u32> = (0..target_length).map(|_| target_random.next() as u32 % 40).collect();
+
+        target_value.sort_unstable();
+        target_value.dedup();
+        target_value
+    }
+
+    #[test]
+    fn set_operations_match_filters() {
+        let mut target_random = Xorshift::new(0xBADC_0FFE_E0DD_F00D);
+
+        for target_left_length in 0..30 {
+            for target_right_length in 0..30 {
+                let target_left = sorted_set(&mut target_random, target_left_length);
+                let target_right = sorted_set(&mut target_random, target_right_length);
+
+                let mut target_output = vec![0; target_left.len() + target_right.len()];
+
+                let target_intersection: Vec<u32> = target_left.iter().copied().filter(|target_value| target_right.contains(target_value)).collect();
+                let target_difference: Vec<u32> = target_left.iter().copied().filter(|target_value| !target_right.contains(target_value)).collect();
+
+                let mut target_union = [target_left.clone(), target_right.clone()].concat();
+
+                target_union.sort_unstable();
+                target_union.dedup();
+
+                let target_count = intersect_sorted(&target_left, &target_right, &mut target_output);
+                assert_eq!(&target_output[..target_count], &target_intersection[..]);
+
+                let target_count = union_sorted(&target_left, &target_right, &mut target_output);
+                assert_eq!(&target_output[..target_count], &target_union[..]);
+
+                let target_count = difference_sorted(&target_left, &target_right, &mut target_output);
+                assert_eq!(&target_output[..target_count], &target_difference[..]);
+            }
+        }
+    }
+}
@@ -0,0 +1,183 @@
+/*
+    linearity: A library for branchless programming
+    Copyright (C) 2024  advantageous-overtake
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Data-oblivious bitonic sort over slices of any length.
+//!
+//! See [`oblivious_sort`] for more information.
+
+use crate::Select;
+
+use super::compare_exchange_by_key;
+
+/// Sorts `target_slice[target_offset..target_offset + target_length]` into ascending order if `target_ascending` holds,
+/// descending otherwise.
+fn bitonic_sort<T, K, F>(
+    target_slice: &mut [T],
+    target_offset: usize,
+    target_length: usize,
+    target_ascending: bool,
+    target_key: &F,
+) where
+    T: Select,
+
+    K: Ord,
+    F: Fn(&T) -> K,
+{
+    if target_length > 1 {
+        let target_half = target_length / 2;
+
+        // Sorting the halves in opposite directions yields a bitonic sequence.
+        bitonic_sort(target_slice, target_offset, target_half, !target_ascending, target_key);
+        bitonic_sort(target_slice, target_offset + target_half, target_length - target_half, target_ascending, target_key);
+
+        bitonic_merge(target_slice, target_offset, target_length, target_ascending, target_key);
+    }
+}
+
+/// Merges the bitonic sequence `target_slice[target_offset..target_offset + target_length]`.
+///
+/// Lengths which are not a power of two are handled by splitting at the greatest power of two below the length.
+fn bitonic_merge<T, K, F>(
+    target_slice: &mut [T],
+    target_offset: usize,
+    target_length: usize,
+    target_ascending: bool,
+    target_key: &F,
+) where
+    T: Select,
+
+    K: Ord,
+    F: Fn(&T) -> K,
+{
+    if target_length > 1 {
+        let target_stride = 1 << (usize::BITS - 1 - (target_length - 1).leading_zeros());
+
+        for target_index in target_offset..target_offset + target_length - target_stride {
+            // The direction is fixed by the schedule, this branch does not depend on the data.
+            match target_ascending {
+                true => compare_exchange_by_key(target_slice, target_index, target_index + target_stride, target_key),
+                false => compare_exchange_by_key(target_slice, target_index + target_stride, target_index, target_key),
+            }
+        }
+
+        bitonic_merge(target_slice, target_offset, target_stride, target_ascending, target_key);
+        bitonic_merge(target_slice, target_offset + target_stride, target_length - target_stride, target_ascending, target_key);
+    }
+}
+
+/// Sorts `target_slice` in ascending order with a bitonic sorting network.
+///
+/// The sequence of compared positions depends on the length alone, and every comparison writes both positions, so
+/// neither the control flow nor the memory trace depends on the contents of the slice. The sort is not stable and takes
+/// `O(n log² n)` comparisons.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::sort::oblivious_sort;
+///
+/// let mut target_slice = [7u32, 1, 4, 1, 9];
+///
+/// oblivious_sort(&mut target_slice);
+///
+/// assert_eq!(target_slice, [1, 1, 4, 7, 9]);
+/// ```
+#[inline]
+pub fn oblivious_sort<T>(target_slice: &mut [T])
+where
+    T: Select + Ord,
+{
+    oblivious_sort_by_key(target_slice, |target_value| *target_value);
+}
+
+/// Sorts `target_slice` in ascending order of the keys extracted by `target_key`.
+///
+/// See [`oblivious_sort`] for the guarantees provided, `target_key` is evaluated on both operands of every comparison.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::sort::oblivious_sort_by_key;
+///
+/// let mut target_slice = [0x0103u16, 0x0201, 0x0302];
+///
+/// oblivious_sort_by_key(&mut target_slice, |target_value| target_value & 0xFF);
+///
+/// assert_eq!(target_slice, [0x0201, 0x0302, 0x0103]);
+/// ```
+#[inline]
+pub fn oblivious_sort_by_key<T, K, F>(target_slice: &mut [T], target_key: F)
+where
+    T: Select,
+
+    K: Ord,
+    F: Fn(&T) -> K,
+{
+    let target_length = target_slice.len();
+
+    bitonic_sort(target_slice, 0, target_length, true, &target_key);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::random::Xorshift;
+
+    use super::{oblivious_sort, oblivious_sort_by_key};
+
+    #[test]
+    fn oblivious_sort_matches_sort() {
+        let mut target_random = Xorshift::new(0x0DDB_1A5E_5BAD_5EED);
+
+        for target_length in 0..100 {
+            let mut target_value: Vec<i64> = (0..target_length)
+                .map(|_| target_random.next() as i64 % 50)
+                .collect();
+            let mut target_expect = target_value.clone();
+
+            target_expect.sort_unstable();
+            oblivious_sort(&mut target_value);
+
+            assert_eq!(target_expect, target_value);
+        }
+    }
+
+    #[test]
+    fn oblivious_sort_by_key_matches_sort_by_key() {
+        let mut target_random = Xorshift::new(0xFEED_FACE_CAFE_BEEF);
+
+        for target_length in 0..100 {
+            let mut target_value: Vec<u32> = (0..target_length)
+                .map(|_| target_random.next() as u32)
+                .collect();
+
+            let mut target_expect = target_value.clone();
+
+            oblivious_sort_by_key(&mut target_value, |target_element| target_element >> 24);
+
+            assert!(target_value.windows(2).all(|target_pair| target_pair[0] >> 24 <= target_pair[1] >> 24));
+
+            // The output must be a permutation of the input, neither dropping nor duplicating elements.
+            let mut target_sorted = target_value.clone();
+
+            target_expect.sort_unstable();
+            target_sorted.sort_unstable();
+
+            assert_eq!(target_expect, target_sorted);
+        }
+    }
+}
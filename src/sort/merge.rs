@@ -0,0 +1,267 @@
+/*
+    linearity: A library for branchless programming
+    Copyright (C) 2024  advantageous-overtake
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Branchless merging of sorted slices and the merge sort built on it.
+//!
+//! See [`merge_branchless`] and [`merge_sort_branchless`] for more information.
+
+use crate::{Filter, Linearity, RefExt};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Merges the sorted slices `target_left` and `target_right` into `target_output`.
+///
+/// Every step writes the smaller head, chosen with [`RefExt::select`], and advances both cursors by masked increments.
+/// Ties are resolved in favour of `target_left`, so the merge is stable.
+///
+/// # Panics
+///
+/// Panics if the length of `target_output` is not the sum of the lengths of both inputs.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::sort::merge_branchless;
+///
+/// let mut target_output = [0; 7];
+///
+/// merge_branchless(&[1, 4, 4, 9], &[2, 4, 10], &mut target_output);
+///
+/// assert_eq!(target_output, [1, 2, 4, 4, 4, 9, 10]);
+/// ```
+#[inline]
+pub fn merge_branchless<T>(target_left: &[T], target_right: &[T], target_output: &mut [T])
+where
+    T: Copy + Ord,
+{
+    assert_eq!(target_output.len(), target_left.len() + target_right.len());
+
+    let mut target_left_index = 0;
+    let mut target_right_index = 0;
+    let mut target_output_index = 0;
+
+    while target_left_index < target_left.len() && target_right_index < target_right.len() {
+        let target_left_head = &target_left[target_left_index];
+        let target_right_head = &target_right[target_right_index];
+
+        let target_dependence = target_right_head < target_left_head;
+
+        target_output[target_output_index] = *target_left_head.select(target_right_head, target_dependence);
+
+        target_left_index += 1usize.filter::<usize>(Filter::new(!target_dependence));
+        target_right_index += 1usize.filter::<usize>(Filter::new(target_dependence));
+        target_output_index += 1;
+    }
+
+    // At most one of both tails is non-empty.
+    let (target_output, target_tail) = target_output.split_at_mut(target_output_index + target_left.len() - target_left_index);
+
+    target_output[target_output_index..].copy_from_slice(&target_left[target_left_index..]);
+    target_tail.copy_from_slice(&target_right[target_right_index..]);
+}
+
+/// Merges every pair of adjacent runs of length `target_width` from `target_source` into `target_destination`.
+#[inline]
+fn merge_pass<T>(target_source: &[T], target_destination: &mut [T], target_width: usize)
+where
+    T: Copy + Ord,
+{
+    let target_chunks = target_source
+        .chunks(2 * target_width)
+        .zip(target_destination.chunks_mut(2 * target_width));
+
+    for (target_source, target_destination) in target_chunks {
+        let (target_left, target_right) = target_source.split_at(target_width.min(target_source.len()));
+
+        merge_branchless(target_left, target_right, target_destination);
+    }
+}
+
+/// Sorts `target_slice` in ascending order with a bottom-up merge sort, using `target_buffer` as scratch space.
+///
+/// Every merge is performed by [`merge_branchless`], so the sort is stable.
+///
+/// # Panics
+///
+/// Panics if `target_buffer` is shorter than `target_slice`.
+///
+/// # Examples
+///
+/// ```
+/// use core::cmp::Ordering;
+///
+/// use linearity::sort::merge_sort_branchless;
+///
+/// // Ordered by `key` only, so elements with equal keys keep their relative order.
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// struct Entry {
+///     key: u8,
+///     payload: char,
+/// }
+///
+/// impl PartialOrd for Entry {
+///     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+///         Some(self.cmp(other))
+///     }
+/// }
+///
+/// impl Ord for Entry {
+///     fn cmp(&self, other: &Self) -> Ordering {
+///         self.key.cmp(&other.key)
+///     }
+/// }
+///
+/// let entry = |key, payload| Entry { key, payload };
+///
+/// let mut target_slice = [entry(3, 'a'), entry(1, 'b'), entry(3, 'c'), entry(2, 'd'), entry(1, 'e')];
+/// let mut target_buffer = [entry(0, ' '); 5];
+///
+/// merge_sort_branchless(&mut target_slice, &mut target_buffer);
+///
+/// assert_eq!(target_slice.map(|target_entry| target_entry.payload), ['b', 'e', 'd', 'a', 'c']);
+/// ```
+pub fn merge_sort_branchless<T>(target_slice: &mut [T], target_buffer: &mut [T])
+where
+    T: Copy + Ord,
+{
+    let target_length = target_slice.len();
+    let target_buffer = &mut target_buffer[..target_length];
+
+    let mut target_width = 1;
+    let mut target_swapped = false;
+
+    // Runs ping-pong between the slice and the buffer, one pass per doubling of their width.
+    while target_width < target_length {
+        match target_swapped {
+            false => merge_pass(target_slice, target_buffer, target_width),
+            true => merge_pass(target_buffer, target_slice, target_width),
+        }
+
+        target_swapped = !target_swapped;
+        target_width *= 2;
+    }
+
+    if target_swapped {
+        target_slice.copy_from_slice(target_buffer);
+    }
+}
+
+/// Sorts `target_slice` in ascending order, as per [`merge_sort_branchless`] with an allocated buffer.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::sort::sort_stable_branchless;
+///
+/// let mut target_slice = [9, 2, 7, 2];
+///
+/// sort_stable_branchless(&mut target_slice);
+///
+/// assert_eq!(target_slice, [2, 2, 7, 9]);
+/// ```
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn sort_stable_branchless<T>(target_slice: &mut [T])
+where
+    T: Copy + Ord,
+{
+    let mut target_buffer: Vec<T> = target_slice.to_vec();
+
+    merge_sort_branchless(target_slice, &mut target_buffer);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::random::Xorshift;
+    use core::cmp::Ordering;
+
+    use super::{merge_branchless, merge_sort_branchless};
+
+    /// An element ordered by `key` only, carrying a `payload` that ties do not look at.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Keyed {
+        key: u8,
+        payload: usize,
+    }
+
+    impl PartialOrd for Keyed {
+        fn partial_cmp(&self, target_other: &Self) -> Option<Ordering> {
+            Some(self.cmp(target_other))
+        }
+    }
+
+    impl Ord for Keyed {
+        fn cmp(&self, target_other: &Self) -> Ordering {
+            self.key.cmp(&target_other.key)
+        }
+    }
+
+
+    #[test]
+    fn merge_branchless_matches_sort() {
+        let mut target_random = Xorshift::new(0xC0FF_EE00_D15E_A5E5);
+
+        for target_left_length in 0..20 {
+            for target_right_length in 0..20 {
+                let mut target_left: Vec<u8> = (0..target_left_length).map(|_| target_random.next() as u8 % 8).collect();
+                let mut target_right: Vec<u8> = (0..target_right_length).map(|_| target_random.next() as u8 % 8).collect();
+
+                target_left.sort_unstable();
+                target_right.sort_unstable();
+
+                let mut target_value = vec![0; target_left_length + target_right_length];
+                let mut target_expect = [target_left.clone(), target_right.clone()].concat();
+
+                target_expect.sort_unstable();
+                merge_branchless(&target_left, &target_right, &mut target_value);
+
+                assert_eq!(target_expect, target_value);
+            }
+        }
+    }
+
+    #[test]
+    fn merge_sort_branchless_is_stable() {
+        let mut target_random = Xorshift::new(0x5EED_0000_0000_0001);
+
+        for target_length in (0..100).chain([1000, 1025]) {
+            // The payload records the original position, which ties must preserve.
+            let mut target_value: Vec<Keyed> = (0..target_length)
+                .map(|target_index| Keyed {
+                    key: target_random.next() as u8 % 16,
+                    payload: target_index,
+                })
+                .collect();
+            let mut target_buffer = vec![Keyed { key: 0, payload: 0 }; target_length];
+
+            merge_sort_branchless(&mut target_value, &mut target_buffer);
+
+            // `Keyed` equality ignores the payload, so the order is checked on `(key, payload)` pairs.
+            assert!(target_value.windows(2).all(|target_pair| {
+                (target_pair[0].key, target_pair[0].payload) < (target_pair[1].key, target_pair[1].payload)
+            }));
+
+            let mut target_payloads: Vec<usize> = target_value.iter().map(|target_element| target_element.payload).collect();
+
+            target_payloads.sort_unstable();
+
+            assert!(target_payloads.into_iter().eq(0..target_length));
+        }
+    }
+}
@@ -0,0 +1,69 @@
+/*
+    linearity: A library for branchless programming
+    Copyright (C) 2024  advantageous-overtake
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Branchless and data-oblivious sorting.
+
+pub mod bitonic;
+pub mod merge;
+pub mod network;
+pub mod quick;
+pub mod radix;
+
+pub use bitonic::{oblivious_sort, oblivious_sort_by_key};
+pub use merge::{merge_branchless, merge_sort_branchless};
+pub use network::sort_network;
+pub use quick::{partition_branchless, sort_unstable_branchless};
+pub use radix::{radix_sort, radix_sort_by_key};
+
+#[cfg(feature = "alloc")]
+pub use merge::sort_stable_branchless;
+
+use crate::Select;
+
+/// Orders the elements at `target_left` and `target_right` so that the smaller one comes first.
+///
+/// Both slots are written regardless of the outcome, the minimum and maximum being chosen with [`Select::select`].
+#[inline(always)]
+pub(crate) fn compare_exchange<T>(target_slice: &mut [T], target_left: usize, target_right: usize)
+where
+    T: Select + Ord,
+{
+    compare_exchange_by_key(target_slice, target_left, target_right, &|target_value: &T| *target_value);
+}
+
+/// Same as [`compare_exchange`], but compares the keys extracted by `target_key`.
+#[inline(always)]
+pub(crate) fn compare_exchange_by_key<T, K, F>(
+    target_slice: &mut [T],
+    target_left: usize,
+    target_right: usize,
+    target_key: &F,
+) where
+    T: Select,
+
+    K: Ord,
+    F: Fn(&T) -> K,
+{
+    let target_lower = target_slice[target_left];
+    let target_upper = target_slice[target_right];
+
+    let target_dependence = target_key(&target_upper) < target_key(&target_lower);
+
+    target_slice[target_left] = T::select(target_lower, target_upper, target_dependence);
+    target_slice[target_right] = T::select(target_upper, target_lower, target_dependence);
+}
@@ -0,0 +1,307 @@
+/*
+    linearity: A library for branchless programming
+    Copyright (C) 2024  advantageous-overtake
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Sorting networks for fixed-size arrays.
+//!
+//! See [`sort_network`] for more information.
+
+use crate::Select;
+
+use super::compare_exchange;
+
+/// Size-optimal networks, indexed by their width.
+const NETWORK_TABLE: [&[(usize, usize)]; 9] = [
+    &[],
+    &[],
+    &[(0, 1)],
+    &[(0, 2), (0, 1), (1, 2)],
+    &[(0, 2), (1, 3), (0, 1), (2, 3), (1, 2)],
+    &[(0, 3), (1, 4), (0, 2), (1, 3), (0, 1), (2, 4), (1, 2), (3, 4), (2, 3)],
+    &[
+        (0, 5), (1, 3), (2, 4), (1, 2), (3, 4), (0, 3),
+        (2, 5), (0, 1), (2, 3), (4, 5), (1, 2), (3, 4),
+    ],
+    &[
+        (0, 6), (2, 3), (4, 5), (0, 2), (1, 4), (3, 6), (0, 1), (2, 5),
+        (3, 4), (1, 2), (4, 6), (2, 3), (4, 5), (1, 2), (3, 4), (5, 6),
+    ],
+    &[
+        (0, 2), (1, 3), (4, 6), (5, 7), (0, 4), (1, 5), (2, 6), (3, 7), (0, 1), (2, 3),
+        (4, 5), (6, 7), (2, 4), (3, 5), (1, 4), (3, 6), (1, 2), (3, 4), (5, 6),
+    ],
+];
+
+/// The widest network available.
+pub const NETWORK_MAX: usize = 32;
+
+/// Applies Batcher's odd-even merge sort to `target_slice`.
+///
+/// Every condition below depends on indices alone, so the comparator schedule is fixed for a given length.
+#[inline(always)]
+pub(crate) fn odd_even_merge_sort<T>(target_slice: &mut [T])
+where
+    T: Select + Ord,
+{
+    let target_length = target_slice.len();
+    let mut target_span = 1;
+
+    while target_span < target_length {
+        let mut target_stride = target_span;
+
+        while target_stride >= 1 {
+            let mut target_offset = target_stride % target_span;
+
+            while target_offset + target_stride < target_length {
+                let target_limit = target_stride.min(target_length - target_offset - target_stride);
+
+                for target_index in 0..target_limit {
+                    let target_left = target_index + target_offset;
+                    let target_right = target_left + target_stride;
+
+                    if target_left / (2 * target_span) == target_right / (2 * target_span) {
+                        compare_exchange(target_slice, target_left, target_right);
+                    }
+                }
+
+                target_offset += 2 * target_stride;
+            }
+
+            target_stride /= 2;
+        }
+
+        target_span *= 2;
+    }
+}
+
+/// Sorts `target_array` in ascending order with a sorting network.
+///
+/// Widths up to 8 use size-optimal networks, wider ones up to [`NETWORK_MAX`] use Batcher's odd-even merge sort. In
+/// both cases the sequence of comparators depends on `N` alone, and every comparator is a branchless minimum and
+/// maximum pair, so the sort is data-oblivious.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::sort::sort_network;
+///
+/// let mut target_array = [9, -3, 5, 0, 12, -3];
+///
+/// sort_network(&mut target_array);
+///
+/// assert_eq!(target_array, [-3, -3, 0, 5, 9, 12]);
+/// ```
+#[inline]
+pub fn sort_network<T, const N: usize>(target_array: &mut [T; N])
+where
+    T: Select + Ord,
+{
+    const { assert!(N <= NETWORK_MAX, "no sorting network is available for this width") };
+
+    match NETWORK_TABLE.get(N) {
+        Some(target_network) => {
+            for &(target_left, target_right) in target_network.iter() {
+                compare_exchange(target_array, target_left, target_right);
+            }
+        }
+        None => odd_even_merge_sort(target_array),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, cmp::Ordering};
+
+    use crate::{random::Xorshift, Select};
+
+    use super::{odd_even_merge_sort, sort_network};
+    use paste::item;
+
+    std::thread_local! {
+        static COMPARATORS: RefCell<Vec<(usize, usize)>> = const { RefCell::new(Vec::new()) };
+    }
+
+    /// An element holding its position, recording every comparator it goes through without ever being moved.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Probe(usize);
+
+    impl Select for Probe {
+        fn select(target_left: Self, target_right: Self, target_dependence: bool) -> Self {
+            Probe(usize::select(target_left.0, target_right.0, target_dependence))
+        }
+    }
+
+    impl PartialOrd for Probe {
+        fn partial_cmp(&self, target_other: &Self) -> Option<Ordering> {
+            Some(self.cmp(target_other))
+        }
+
+        /// A comparator asks whether its upper element is less than its lower one.
+        fn lt(&self, target_other: &Self) -> bool {
+            COMPARATORS.with_borrow_mut(|target_comparators| target_comparators.push((target_other.0, self.0)));
+
+            false
+        }
+    }
+
+    impl Ord for Probe {
+        fn cmp(&self, _: &Self) -> Ordering {
+            Ordering::Equal
+        }
+    }
+
+    /// Returns the comparators `sort_network` applies at width `N`.
+    fn comparators<const N: usize>() -> Vec<(usize, usize)> {
+        let mut target_array: [Probe; N] = core::array::from_fn(Probe);
+
+        COMPARATORS.with_borrow_mut(Vec::clear);
+        sort_network(&mut target_array);
+
+        COMPARATORS.take()
+    }
+
+    /// Checks the 0-1 principle exhaustively at width `N`, 64 binary sequences at a time.
+    ///
+    /// Every lane of a word holds one sequence, so a comparator is a bitwise minimum and maximum over words.
+    fn zero_one_exhaustive<const N: usize>() {
+        const LANES: [u64; 6] = [
+            0xAAAA_AAAA_AAAA_AAAA,
+            0xCCCC_CCCC_CCCC_CCCC,
+            0xF0F0_F0F0_F0F0_F0F0,
+            0xFF00_FF00_FF00_FF00,
+            0xFFFF_0000_FFFF_0000,
+            0xFFFF_FFFF_0000_0000,
+        ];
+
+        let target_comparators = comparators::<N>();
+
+        for target_block in 0..1u64 << (N - LANES.len()) {
+            let mut target_words: [u64; N] = core::array::from_fn(|target_index| match LANES.get(target_index) {
+                Some(&target_lane) => target_lane,
+                None => 0u64.wrapping_sub(target_block >> (target_index - LANES.len()) & 1),
+            });
+
+            for &(target_left, target_right) in &target_comparators {
+                let target_lower = target_words[target_left];
+                let target_upper = target_words[target_right];
+
+                target_words[target_left] = target_lower & target_upper;
+                target_words[target_right] = target_lower | target_upper;
+            }
+
+            assert!(
+                target_words.windows(2).all(|target_pair| target_pair[0] & !target_pair[1] == 0),
+                "width {N} fails on a sequence of block {target_block:#x}"
+            );
+        }
+    }
+
+    #[test]
+    fn sort_network_zero_one() {
+        zero_one_exhaustive::<8>();
+        zero_one_exhaustive::<12>();
+        zero_one_exhaustive::<16>();
+    }
+
+    #[test]
+    #[ignore = "exhaustive over 2^32 sequences, run through `cargo test --release -- --ignored`"]
+    fn sort_network_zero_one_wide() {
+        zero_one_exhaustive::<17>();
+        zero_one_exhaustive::<18>();
+        zero_one_exhaustive::<19>();
+        zero_one_exhaustive::<20>();
+        zero_one_exhaustive::<21>();
+        zero_one_exhaustive::<22>();
+        zero_one_exhaustive::<23>();
+        zero_one_exhaustive::<24>();
+        zero_one_exhaustive::<25>();
+        zero_one_exhaustive::<26>();
+        zero_one_exhaustive::<27>();
+        zero_one_exhaustive::<28>();
+        zero_one_exhaustive::<29>();
+        zero_one_exhaustive::<30>();
+        zero_one_exhaustive::<31>();
+        zero_one_exhaustive::<32>();
+    }
+
+    macro_rules! impl_tests {
+        (
+            $(
+                $target_width:literal
+            )+
+        ) => {
+            item! {
+                $(
+                    #[test]
+                    fn [< sort_network_ $target_width >]() {
+                        /*
+                            Up to 16 wide, every binary sequence is sorted, which by the 0-1 principle proves the
+                            network correct. Wider networks are only sampled here, see `sort_network_zero_one_wide`.
+                         */
+                        let target_limit: u64 = 1 << $target_width.min(16);
+                        let mut target_random = Xorshift::new(0x9E37_79B9_7F4A_7C15);
+
+                        for target_sample in 0..target_limit {
+                            let target_bits = if $target_width <= 16 {
+                                target_sample
+                            } else {
+                                target_random.next()
+                            };
+
+                            let mut target_value: [u8; $target_width] =
+                                core::array::from_fn(|target_index| (target_bits >> target_index) as u8 & 1);
+                            let mut target_expect = target_value;
+
+                            target_expect.sort_unstable();
+                            sort_network(&mut target_value);
+
+                            assert_eq!(target_expect, target_value);
+                        }
+                    }
+                )+
+            }
+        };
+    }
+
+    impl_tests!(0 1 2 3 4 5 6 7 8 9 12 15 16 17 24 31 32);
+
+    #[test]
+    fn sort_network_composites() {
+        let mut target_value = [(3u8, -1i32), (1, 5), (3, -7), (0, 0), (1, 2)];
+        let mut target_expect = target_value;
+
+        target_expect.sort_unstable();
+        sort_network(&mut target_value);
+
+        assert_eq!(target_expect, target_value);
+    }
+
+    #[test]
+    fn odd_even_merge_sort_slices() {
+        let mut target_random = Xorshift::new(0x1234_5678);
+
+        for target_length in 0..64 {
+            let mut target_value: Vec<i32> = (0..target_length).map(|_| target_random.next() as i32).collect();
+            let mut target_expect = target_value.clone();
+
+            target_expect.sort_unstable();
+            odd_even_merge_sort(&mut target_value);
+
+            assert_eq!(target_expect, target_value);
+        }
+    }
+}
@@ -0,0 +1,253 @@
+/*
+    linearity: A library for branchless programming
+    Copyright (C) 2024  advantageous-overtake
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Branchless partitioning and the quicksort built on it.
+//!
+//! See [`partition_branchless`] and [`sort_unstable_branchless`] for more information.
+
+use crate::{Filter, Linearity, Select};
+
+use super::sort_network;
+
+/// Slices up to this length are sorted with a sorting network.
+const SMALL_SORT_THRESHOLD: usize = 16;
+
+/// Moves the elements satisfying `target_predicate` to the front of `target_slice`, returning their count.
+///
+/// This is Lomuto's scheme performed as a cyclic permutation: a gap travels along the slice, and the only decision
+/// taken per element is how far the partition index advances.
+#[inline(always)]
+fn partition_by<T, F>(target_slice: &mut [T], target_predicate: F) -> usize
+where
+    T: Copy,
+    F: Fn(&T) -> bool,
+{
+    let Some(&target_first) = target_slice.first() else {
+        return 0;
+    };
+
+    let mut target_gap = 0;
+    let mut target_partition = 0;
+
+    for target_index in 1..target_slice.len() {
+        let target_value = target_slice[target_index];
+
+        target_slice[target_gap] = target_slice[target_partition];
+        target_slice[target_partition] = target_value;
+
+        target_gap = target_index;
+        target_partition += 1usize.filter::<usize>(Filter::new(target_predicate(&target_value)));
+    }
+
+    // Close the gap with the element lifted out at the start.
+    target_slice[target_gap] = target_slice[target_partition];
+    target_slice[target_partition] = target_first;
+
+    target_partition + 1usize.filter::<usize>(Filter::new(target_predicate(&target_first)))
+}
+
+/// Partitions `target_slice` so that every element less than `target_pivot` comes first.
+///
+/// Returns the number of elements less than `target_pivot`, the relative order within each side is unspecified.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::sort::partition_branchless;
+///
+/// let mut target_slice = [8, 1, 9, 2, 7, 3];
+///
+/// let target_middle = partition_branchless(&mut target_slice, &5);
+///
+/// assert_eq!(target_middle, 3);
+/// assert!(target_slice[..target_middle].iter().all(|&target_value| target_value < 5));
+/// assert!(target_slice[target_middle..].iter().all(|&target_value| target_value >= 5));
+/// ```
+#[inline]
+pub fn partition_branchless<T>(target_slice: &mut [T], target_pivot: &T) -> usize
+where
+    T: Copy + Ord,
+{
+    partition_by(target_slice, |target_value| target_value < target_pivot)
+}
+
+/// Sorts a slice no longer than [`SMALL_SORT_THRESHOLD`] with the sorting network of its exact length.
+#[inline(always)]
+fn small_sort<T>(target_slice: &mut [T])
+where
+    T: Select + Ord,
+{
+    macro_rules! dispatch {
+        (
+            $(
+                $target_width:literal
+            )+
+        ) => {
+            match target_slice.len() {
+                $(
+                    $target_width => sort_network::<T, $target_width>(target_slice.try_into().unwrap()),
+                )+
+                _ => unreachable!(),
+            }
+        };
+    }
+
+    dispatch!(0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15 16);
+}
+
+/// Sorts `target_slice`, falling back to [`slice::sort_unstable`] once partitions are nested `target_limit` deep.
+///
+/// As in introsort, `target_limit` bounds the depth of partitioning regardless of how balanced each partition is.
+fn quicksort<T>(mut target_slice: &mut [T], mut target_limit: u32)
+where
+    T: Select + Ord,
+{
+    while target_slice.len() > SMALL_SORT_THRESHOLD {
+        if target_limit == 0 {
+            target_slice.sort_unstable();
+
+            return;
+        }
+
+        target_limit -= 1;
+
+        let target_length = target_slice.len();
+
+        let mut target_sample = [
+            target_slice[0],
+            target_slice[target_length / 2],
+            target_slice[target_length - 1],
+        ];
+
+        sort_network(&mut target_sample);
+
+        let target_pivot = target_sample[1];
+        let target_middle = partition_branchless(target_slice, &target_pivot);
+
+        if target_middle == 0 {
+            // Nothing is less than the pivot, so it is the minimum, gather every copy of it and skip them.
+            let target_equal = partition_by(target_slice, |target_value| *target_value <= target_pivot);
+
+            target_slice = &mut target_slice[target_equal..];
+
+            continue;
+        }
+
+        let (target_left, target_right) = target_slice.split_at_mut(target_middle);
+
+        // Recurse into the shorter side, so the stack depth stays logarithmic.
+        if target_left.len() < target_right.len() {
+            quicksort(target_left, target_limit);
+            target_slice = target_right;
+        } else {
+            quicksort(target_right, target_limit);
+            target_slice = target_left;
+        }
+    }
+
+    small_sort(target_slice);
+}
+
+/// Sorts `target_slice` in ascending order with a quicksort free of data-dependent branches in its hot loops.
+///
+/// Partitioning uses [`partition_branchless`] and short runs are sorted with [`sort_network`]. Once partitions are
+/// nested twice the logarithm of the length deep, as in introsort, the remainder is handed to
+/// [`slice::sort_unstable`], bounding the running time to `O(n log n)`. The sort is
+/// not stable.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::sort::sort_unstable_branchless;
+///
+/// let mut target_slice = [5u8, 200, 3, 3, 91, 0, 17];
+///
+/// sort_unstable_branchless(&mut target_slice);
+///
+/// assert_eq!(target_slice, [0, 3, 3, 5, 17, 91, 200]);
+/// ```
+#[inline]
+pub fn sort_unstable_branchless<T>(target_slice: &mut [T])
+where
+    T: Select + Ord,
+{
+    let target_limit = 2 * (usize::BITS - target_slice.len().leading_zeros());
+
+    quicksort(target_slice, target_limit);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::random::Xorshift;
+
+    use super::{partition_branchless, sort_unstable_branchless};
+
+    #[test]
+    fn partition_branchless_splits() {
+        let mut target_random = Xorshift::new(0xA5A5_5A5A_0F0F_F0F0);
+
+        for target_length in 0..64 {
+            let mut target_value: Vec<u16> = (0..target_length)
+                .map(|_| target_random.next() as u16 % 32)
+                .collect();
+            let mut target_expect = target_value.clone();
+
+            let target_middle = partition_branchless(&mut target_value, &16);
+
+            assert!(target_value[..target_middle].iter().all(|&target_element| target_element < 16));
+            assert!(target_value[target_middle..].iter().all(|&target_element| target_element >= 16));
+
+            // The partition is a permutation of the input.
+            target_expect.sort_unstable();
+            target_value.sort_unstable();
+
+            assert_eq!(target_expect, target_value);
+        }
+    }
+
+    #[test]
+    fn sort_unstable_branchless_matches_sort() {
+        let mut target_random = Xorshift::new(0x0123_4567_89AB_CDEF);
+
+        for target_length in (0..200).chain([1000, 4096]) {
+            for target_modulus in [2, 17, u64::MAX] {
+                let mut target_value: Vec<i32> = (0..target_length)
+                    .map(|_| (target_random.next() % target_modulus) as i32)
+                    .collect();
+                let mut target_expect = target_value.clone();
+
+                target_expect.sort_unstable();
+                sort_unstable_branchless(&mut target_value);
+
+                assert_eq!(target_expect, target_value);
+            }
+        }
+    }
+
+    #[test]
+    fn sort_unstable_branchless_sorted_inputs() {
+        let mut target_ascending: Vec<u64> = (0..2000).collect();
+        let mut target_descending: Vec<u64> = (0..2000).rev().collect();
+
+        sort_unstable_branchless(&mut target_ascending);
+        sort_unstable_branchless(&mut target_descending);
+
+        assert_eq!(target_ascending, target_descending);
+        assert!(target_ascending.windows(2).all(|target_pair| target_pair[0] <= target_pair[1]));
+    }
+}
@@ -0,0 +1,226 @@
+/*
+    linearity: A library for branchless programming
+    Copyright (C) 2024  advantageous-overtake
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Least significant digit radix sort for primitive keys.
+//!
+//! See [`radix_sort`] for more information.
+
+use crate::primitive::{
+    cast::Cast,
+    op::binary::{BitShr, BitXor},
+    Primitive,
+};
+
+/// The number of bits per digit.
+const RADIX_BITS: u8 = 8;
+
+/// The number of distinct digits.
+const RADIX: usize = 1 << RADIX_BITS;
+
+/// Maps `target_key` to its unsigned counterpart, with the same ordering.
+///
+/// For signed types the sign bit is flipped, moving negative values below positive ones. `K::MIN` is exactly the sign
+/// bit for signed types and zero for unsigned ones, so it serves as the bias either way.
+#[inline(always)]
+fn ordered_bits<K>(target_key: K) -> K::Unsigned
+where
+    K: Primitive + Cast<K::Unsigned>,
+    K::Unsigned: BitXor<Operand = K::Unsigned, Output = K::Unsigned>,
+{
+    let target_bias: K::Unsigned = K::MIN.cast();
+
+    target_key.cast().xor(target_bias)
+}
+
+/// Scatters `target_source` into `target_destination` by the digit at `target_shift`, preserving relative order.
+#[inline(always)]
+fn radix_pass<T, K, F>(target_source: &[T], target_destination: &mut [T], target_shift: u8, target_key: &F)
+where
+    T: Copy,
+    K: Primitive + Cast<K::Unsigned>,
+    K::Unsigned: BitXor<Operand = K::Unsigned, Output = K::Unsigned> + BitShr<Operand = K::Unsigned, Output = K::Unsigned>,
+    K::Unsigned: Cast<usize>,
+    u8: Cast<K::Unsigned>,
+    F: Fn(&T) -> K,
+{
+    let target_digit = |target_value: &T| {
+        let target_bits = ordered_bits(target_key(target_value)).shr(target_shift.cast());
+
+        target_bits.cast() % RADIX
+    };
+
+    let mut target_offsets = [0usize; RADIX];
+
+    for target_value in target_source {
+        target_offsets[target_digit(target_value)] += 1;
+    }
+
+    // Turn the histogram into the starting offset of every digit.
+    let mut target_total = 0;
+
+    for target_offset in target_offsets.iter_mut() {
+        let target_count = *target_offset;
+
+        *target_offset = target_total;
+        target_total += target_count;
+    }
+
+    for target_value in target_source {
+        let target_offset = &mut target_offsets[target_digit(target_value)];
+
+        target_destination[*target_offset] = *target_value;
+        *target_offset += 1;
+    }
+}
+
+/// Sorts `target_slice` by the primitive keys extracted by `target_key`, using `target_buffer` as scratch space.
+///
+/// The sort is stable and takes one pass over the slice per byte of `K`. Keys are mapped to [`Primitive::Unsigned`]
+/// with their sign bit flipped, so negative keys order below positive ones. Neither histogramming nor scattering
+/// branches on the keys.
+///
+/// # Panics
+///
+/// Panics if `target_buffer` is shorter than `target_slice`.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::sort::radix_sort_by_key;
+///
+/// let mut target_slice = [(-3i16, 'a'), (7, 'b'), (-3, 'c'), (0, 'd')];
+/// let mut target_buffer = [(0, ' '); 4];
+///
+/// radix_sort_by_key(&mut target_slice, &mut target_buffer, |target_record| target_record.0);
+///
+/// assert_eq!(target_slice, [(-3, 'a'), (-3, 'c'), (0, 'd'), (7, 'b')]);
+/// ```
+pub fn radix_sort_by_key<T, K, F>(target_slice: &mut [T], target_buffer: &mut [T], target_key: F)
+where
+    T: Copy,
+    K: Primitive + Cast<K::Unsigned>,
+    K::Unsigned: BitXor<Operand = K::Unsigned, Output = K::Unsigned> + BitShr<Operand = K::Unsigned, Output = K::Unsigned>,
+    K::Unsigned: Cast<usize>,
+    u8: Cast<K::Unsigned>,
+    F: Fn(&T) -> K,
+{
+    let target_length = target_slice.len();
+    let target_buffer = &mut target_buffer[..target_length];
+
+    let mut target_swapped = false;
+
+    for target_shift in (0..K::BITS).step_by(RADIX_BITS as usize) {
+        match target_swapped {
+            false => radix_pass(target_slice, target_buffer, target_shift, &target_key),
+            true => radix_pass(target_buffer, target_slice, target_shift, &target_key),
+        }
+
+        target_swapped = !target_swapped;
+    }
+
+    if target_swapped {
+        target_slice.copy_from_slice(target_buffer);
+    }
+}
+
+/// Sorts `target_slice` in ascending order, using `target_buffer` as scratch space.
+///
+/// See [`radix_sort_by_key`] for more information.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::sort::radix_sort;
+///
+/// let mut target_slice = [300i32, -1, 42, i32::MIN, 0];
+/// let mut target_buffer = [0; 5];
+///
+/// radix_sort(&mut target_slice, &mut target_buffer);
+///
+/// assert_eq!(target_slice, [i32::MIN, -1, 0, 42, 300]);
+/// ```
+#[inline]
+pub fn radix_sort<T>(target_slice: &mut [T], target_buffer: &mut [T])
+where
+    T: Primitive + Cast<T::Unsigned>,
+    T::Unsigned: BitXor<Operand = T::Unsigned, Output = T::Unsigned> + BitShr<Operand = T::Unsigned, Output = T::Unsigned>,
+    T::Unsigned: Cast<usize>,
+    u8: Cast<T::Unsigned>,
+{
+    radix_sort_by_key(target_slice, target_buffer, |target_value| *target_value);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::random::Xorshift;
+    use crate::primitive::primitive_list;
+    use paste::item;
+
+    macro_rules! impl_radix_sort {
+        (
+            $(
+                $target_ty:ident
+            )+
+        ) => {
+            item!(
+                $(
+                    #[test]
+                    fn [< radix_sort_ $target_ty:snake >] () {
+                        use super::radix_sort;
+
+                        let mut target_random = Xorshift::new(0xDEAD_BEEF_0BAD_F00D);
+
+                        for target_length in [0, 1, 2, 7, 100, 1000] {
+                            let mut target_value: Vec<$target_ty> = (0..target_length)
+                                .map(|_| target_random.value::<$target_ty>())
+                                .chain([<$target_ty>::MIN, <$target_ty>::MAX, 0])
+                                .collect();
+                            let mut target_buffer = target_value.clone();
+                            let mut target_expect = target_value.clone();
+
+                            target_expect.sort_unstable();
+                            radix_sort(&mut target_value, &mut target_buffer);
+
+                            assert_eq!(target_expect, target_value);
+                        }
+                    }
+                )+
+            );
+        };
+    }
+
+    primitive_list!(impl_radix_sort);
+
+    #[test]
+    fn radix_sort_by_key_is_stable() {
+        use super::radix_sort_by_key;
+
+        let mut target_random = Xorshift::new(0x1111_2222_3333_4444);
+
+        let mut target_value: Vec<(i8, usize)> = (0..500)
+            .map(|target_index| (target_random.next() as i8, target_index))
+            .collect();
+        let mut target_buffer = target_value.clone();
+        let mut target_expect = target_value.clone();
+
+        target_expect.sort_by_key(|target_record| target_record.0);
+        radix_sort_by_key(&mut target_value, &mut target_buffer, |target_record| target_record.0);
+
+        assert_eq!(target_expect, target_value);
+    }
+}
@@ -0,0 +1,242 @@
+/*
+    linearity: A library for branchless programming
+    Copyright (C) 2024  advantageous-overtake
+
+    This program is free software: you can redistribute it and/or modify
+    it under the terms of the GNU Affero General Public License as published by
+    the Free Software Foundation, either version 3 of the License, or
+    (at your option) any later version.
+
+    This program is distributed in the hope that it will be useful,
+    but WITHOUT ANY WARRANTY; without even the implied warranty of
+    MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+    GNU Affero General Public License for more details.
+
+    You should have received a copy of the GNU Affero General Public License
+    along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Wiping memory in a way the compiler cannot elide.
+//!
+//! See [`secure_zero`] and [`Zeroizing`] for more information.
+
+use core::{
+    ops::{Deref, DerefMut},
+    ptr,
+    sync::atomic::{compiler_fence, Ordering},
+};
+
+use crate::{
+    primitive::{cast::Cast, primitive_list, Primitive},
+    Secret,
+};
+
+/// Overwrites every element of `target_slice` with zero.
+///
+/// Every element is written through [`ptr::write_volatile`], followed by a compiler fence, so that the writes are not
+/// removed even though the memory is never read again.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::secure_zero;
+///
+/// let mut target_key = [0xA5u8; 32];
+///
+/// secure_zero(&mut target_key);
+///
+/// assert_eq!(target_key, [0; 32]);
+/// ```
+#[inline]
+pub fn secure_zero<T>(target_slice: &mut [T])
+where
+    T: Primitive,
+    i8: Cast<T>,
+{
+    for target_element in target_slice.iter_mut() {
+        // SAFETY: `target_element` is a valid, aligned and exclusive reference.
+        unsafe { ptr::write_volatile(target_element, 0i8.cast()) };
+    }
+
+    compiler_fence(Ordering::SeqCst);
+}
+
+/// Trait for values that can be wiped in place.
+pub trait Zeroize {
+    /// Overwrites `self` with zero, see [`secure_zero`].
+    fn zeroize(&mut self);
+}
+
+/// Implements [`Zeroize`] for primitive types.
+macro_rules! impl_zeroize {
+    (
+        $(
+            $target_type:ident
+        )+
+    ) => {
+        $(
+            impl Zeroize for $target_type {
+                #[inline]
+                fn zeroize(&mut self) {
+                    secure_zero(core::slice::from_mut(self));
+                }
+            }
+        )+
+    };
+}
+
+primitive_list!(impl_zeroize);
+
+impl<T> Zeroize for [T]
+where
+    T: Primitive,
+    i8: Cast<T>,
+{
+    #[inline]
+    fn zeroize(&mut self) {
+        secure_zero(self);
+    }
+}
+
+impl<T, const N: usize> Zeroize for [T; N]
+where
+    T: Primitive,
+    i8: Cast<T>,
+{
+    #[inline]
+    fn zeroize(&mut self) {
+        secure_zero(self);
+    }
+}
+
+impl<T> Zeroize for Secret<T>
+where
+    T: Zeroize,
+{
+    #[inline]
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// Wipes the referenced value, which allows guarding borrowed buffers with [`Zeroizing`].
+impl<T> Zeroize for &mut T
+where
+    T: Zeroize + ?Sized,
+{
+    #[inline]
+    fn zeroize(&mut self) {
+        (**self).zeroize();
+    }
+}
+
+/// A guard that wipes its value when dropped.
+///
+/// # Examples
+///
+/// ```
+/// use linearity::Zeroizing;
+///
+/// let mut target_buffer = [0u8; 16];
+///
+/// {
+///     let mut target_guard = Zeroizing::new(&mut target_buffer[..]);
+///
+///     target_guard.fill(0x5A);
+/// }
+///
+/// assert_eq!(target_buffer, [0; 16]);
+/// ```
+pub struct Zeroizing<T>(T)
+where
+    T: Zeroize;
+
+impl<T> Zeroizing<T>
+where
+    T: Zeroize,
+{
+    /// Guards `target_value`.
+    #[inline]
+    pub const fn new(target_value: T) -> Self {
+        Self(target_value)
+    }
+}
+
+impl<T> Deref for Zeroizing<T>
+where
+    T: Zeroize,
+{
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Zeroizing<T>
+where
+    T: Zeroize,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> Drop for Zeroizing<T>
+where
+    T: Zeroize,
+{
+    #[inline]
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{primitive::primitive_list, Secret};
+
+    use super::{secure_zero, Zeroize, Zeroizing};
+
+    macro_rules! impl_zeroize {
+        (
+            $(
+                $target_type:ident
+            )+
+        ) => {
+            paste::item! {
+                $(
+                    #[test]
+                    fn [< zeroize_ $target_type >]() {
+                        let mut target_values = [$target_type::MAX; 7];
+
+                        secure_zero(&mut target_values[2..]);
+
+                        assert_eq!(target_values, [$target_type::MAX, $target_type::MAX, 0, 0, 0, 0, 0]);
+
+                        let mut target_value = $target_type::MIN;
+                        let mut target_secret = Secret::new($target_type::MAX);
+
+                        target_value.zeroize();
+                        target_secret.zeroize();
+
+                        assert_eq!(target_value, 0);
+                        assert_eq!(target_secret.expose(), 0);
+
+                        {
+                            let target_guard = Zeroizing::new(&mut target_values);
+
+                            assert_eq!(target_guard[0], $target_type::MAX);
+                        }
+
+                        assert_eq!(target_values, [0; 7]);
+                    }
+                )+
+            }
+        };
+    }
+
+    primitive_list!(impl_zeroize);
+}